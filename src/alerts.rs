@@ -0,0 +1,311 @@
+// Alert rules, evaluated on every refresh by the explorer and by the alerts daemon alike
+use super::i18n::Language;
+use super::paid_percentage;
+use super::types::{EtherscanBalResp, Loadable, MarketChannel, MarketStatusType};
+
+use adex_domain::BigNum;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Rule {
+    // A channel enters the given status; only the channels of `creator`, if set
+    #[serde(rename_all = "camelCase")]
+    StatusBecomes {
+        creator: Option<String>,
+        status: MarketStatusType,
+    },
+    // The paid share of a channel's deposit crosses the given percentage
+    PaidCrosses { percent: u64 },
+    // The locked on-chain balance drops by more than the given amount
+    LockedDrops { amount: BigNum },
+}
+
+impl Rule {
+    pub fn describe(&self, lang: Language) -> String {
+        match self {
+            Rule::StatusBecomes {
+                creator: Some(creator),
+                status,
+            } => lang.tf(
+                "A channel created by {} becomes {}",
+                &[creator, &lang.t(status.name())],
+            ),
+            Rule::StatusBecomes {
+                creator: None,
+                status,
+            } => lang.tf("Any channel becomes {}", &[&lang.t(status.name())]),
+            Rule::PaidCrosses { percent } => {
+                lang.tf("The paid % of a channel crosses {}%", &[percent])
+            }
+            Rule::LockedDrops { amount } => lang.tf(
+                "The locked on-chain balance drops by more than {}",
+                &[&lang.dai(amount)],
+            ),
+        }
+    }
+}
+
+// The rules, and where their matches are delivered
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertConfig {
+    pub rules: Vec<Rule>,
+    // Browser notifications; ignored by the daemon
+    #[serde(default)]
+    pub notify: bool,
+    // Matches are POSTed as JSON to this URL
+    pub webhook: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Alert {
+    pub time: i64,
+    pub rule: Rule,
+    pub channel: Option<String>,
+    pub message: String,
+}
+
+// What we know about the market at a given refresh
+#[derive(Clone, Copy)]
+pub struct MarketState<'a> {
+    pub channels: &'a [MarketChannel],
+    pub locked_on_chain: Option<&'a BigNum>,
+}
+
+impl<'a> MarketState<'a> {
+    // What the explorer knew before a refresh; what was restored from the cache may be days old,
+    // and comparing with it would fire for everything that changed since, so it doesn't count
+    pub fn before_refresh(
+        channels: &'a Loadable<Vec<MarketChannel>>,
+        balance: &'a Loadable<EtherscanBalResp>,
+    ) -> Self {
+        MarketState {
+            channels: channels.fetched().map(Vec::as_slice).unwrap_or(&[]),
+            locked_on_chain: balance.fetched().map(|resp| &resp.result),
+        }
+    }
+}
+
+// The rules matching the changes from `previous` to `current`; a channel has to be
+// in both of them, so that nothing fires on the first load
+pub fn evaluate(
+    time: i64,
+    lang: Language,
+    rules: &[Rule],
+    previous: MarketState,
+    current: MarketState,
+) -> Vec<Alert> {
+    let previous_by_id = previous
+        .channels
+        .iter()
+        .map(|channel| (channel.id.as_str(), channel))
+        .collect::<HashMap<_, _>>();
+    let pairs = current
+        .channels
+        .iter()
+        .filter_map(|channel| {
+            previous_by_id
+                .get(channel.id.as_str())
+                .map(|before| (*before, channel))
+        })
+        .collect::<Vec<_>>();
+    let alert = |rule: &Rule, channel: Option<&MarketChannel>, message: String| Alert {
+        time,
+        rule: rule.clone(),
+        channel: channel.map(|x| x.id.clone()),
+        message,
+    };
+
+    rules
+        .iter()
+        .flat_map(|rule| match rule {
+            Rule::StatusBecomes { creator, status } => pairs
+                .iter()
+                .filter(|(before, after)| {
+                    creator
+                        .as_ref()
+                        .map(|creator| creator.eq_ignore_ascii_case(&after.creator))
+                        .unwrap_or(true)
+                        && &before.status.status_type != status
+                        && &after.status.status_type == status
+                })
+                .map(|(before, after)| {
+                    alert(
+                        rule,
+                        Some(after),
+                        lang.tf(
+                            "Channel {} went from {} to {}",
+                            &[
+                                &after.id,
+                                &lang.t(before.status.status_type.name()),
+                                &lang.t(after.status.status_type.name()),
+                            ],
+                        ),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            Rule::PaidCrosses { percent } => pairs
+                .iter()
+                .filter(|(before, after)| {
+                    !paid_reached(before, *percent) && paid_reached(after, *percent)
+                })
+                .map(|(_, after)| {
+                    alert(
+                        rule,
+                        Some(after),
+                        lang.tf(
+                            "Channel {} has paid out {} of its deposit",
+                            &[
+                                &after.id,
+                                &paid_percentage(&after.status.balances_sum(), &after.deposit_amount),
+                            ],
+                        ),
+                    )
+                })
+                .collect(),
+            Rule::LockedDrops { amount } => match (previous.locked_on_chain, current.locked_on_chain) {
+                (Some(before), Some(after)) if before > after && &(before - after) > amount => {
+                    vec![alert(
+                        rule,
+                        None,
+                        lang.tf(
+                            "The locked on-chain balance dropped by {}, to {}",
+                            &[&lang.dai(&(before - after)), &lang.dai(after)],
+                        ),
+                    )]
+                }
+                _ => vec![],
+            },
+        })
+        .collect()
+}
+
+fn paid_reached(channel: &MarketChannel, percent: u64) -> bool {
+    &channel.status.balances_sum() * &BigNum::from(100) >= &channel.deposit_amount * &BigNum::from(percent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_fixtures::{dai, fixture};
+
+    fn state(channels: &[MarketChannel]) -> MarketState {
+        MarketState {
+            channels,
+            locked_on_chain: None,
+        }
+    }
+
+    #[test]
+    fn status_becomes() {
+        let before = fixture();
+        let mut after = fixture();
+        after[1].status.status_type = MarketStatusType::Unhealthy;
+        let en = Language::English;
+        let unhealthy = |creator: Option<&str>| Rule::StatusBecomes {
+            creator: creator.map(str::to_string),
+            status: MarketStatusType::Unhealthy,
+        };
+
+        let alerts = evaluate(0, en, &[unhealthy(None)], state(&before), state(&after));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].channel.as_ref(), Some(&after[1].id));
+
+        // creators are case insensitive
+        let mine = unhealthy(Some("0x712E9A2F83C41B0B7D6E5F4A3B2C1D0E9F8A7B6C"));
+        assert_eq!(evaluate(0, en, &[mine], state(&before), state(&after)).len(), 1);
+        let theirs = unhealthy(Some(&after[0].creator));
+        assert!(evaluate(0, en, &[theirs], state(&before), state(&after)).is_empty());
+
+        // nothing fires without a previous state, or once the status stays the same
+        assert!(evaluate(0, en, &[unhealthy(None)], state(&[]), state(&after)).is_empty());
+        assert!(evaluate(0, en, &[unhealthy(None)], state(&after), state(&after)).is_empty());
+    }
+
+    #[test]
+    fn paid_crosses() {
+        let before = fixture();
+        let mut after = fixture();
+        // from 40% to 90% paid
+        let earner = after[0].status.balances.keys().next().cloned().unwrap();
+        after[0].status.balances.insert(earner, dai(900));
+        let rules = [
+            Rule::PaidCrosses { percent: 90 },
+            Rule::PaidCrosses { percent: 95 },
+        ];
+        let en = Language::English;
+
+        // the exhausted channel was already fully paid out, so it doesn't cross anything
+        let alerts = evaluate(0, en, &rules, state(&before), state(&after));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].rule, rules[0]);
+        assert!(evaluate(0, en, &rules, state(&after), state(&after)).is_empty());
+    }
+
+    #[test]
+    fn locked_drops() {
+        let channels = fixture();
+        let rule = [Rule::LockedDrops { amount: dai(100) }];
+        let locked = |amount: &BigNum| -> Vec<Alert> {
+            let before = dai(1000);
+            evaluate(
+                0,
+                Language::English,
+                &rule,
+                MarketState {
+                    channels: &channels,
+                    locked_on_chain: Some(&before),
+                },
+                MarketState {
+                    channels: &channels,
+                    locked_on_chain: Some(amount),
+                },
+            )
+        };
+
+        assert!(locked(&dai(950)).is_empty());
+        assert_eq!(locked(&dai(850)).len(), 1);
+        assert!(locked(&dai(2000)).is_empty());
+    }
+
+    #[test]
+    fn cached_state_never_fires() {
+        let before = fixture();
+        let mut after = fixture();
+        after[1].status.status_type = MarketStatusType::Unhealthy;
+        let balance = EtherscanBalResp { result: dai(1000) };
+        let drained = dai(0);
+        let rules = [
+            Rule::StatusBecomes {
+                creator: None,
+                status: MarketStatusType::Unhealthy,
+            },
+            Rule::LockedDrops { amount: dai(0) },
+        ];
+        let en = Language::English;
+        let current = MarketState {
+            channels: &after,
+            locked_on_chain: Some(&drained),
+        };
+
+        // as restored from the cache on the first load
+        let cached_channels = Loadable::Stale(before.clone(), 0);
+        let cached_balance = Loadable::Stale(balance.clone(), 0);
+        let previous = MarketState::before_refresh(&cached_channels, &cached_balance);
+        assert!(evaluate(0, en, &rules, previous, current).is_empty());
+
+        // once fetched in this session
+        let fetched_channels = Loadable::Ready(before, 0);
+        let fetched_balance = Loadable::Ready(balance, 0);
+        let previous = MarketState::before_refresh(&fetched_channels, &fetched_balance);
+        let alerts = evaluate(0, en, &rules, previous, current);
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(
+            alerts[0].message,
+            format!("Channel {} went from Active to Unhealthy", after[1].id)
+        );
+    }
+}
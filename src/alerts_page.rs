@@ -0,0 +1,219 @@
+// Editing the alert rules, and delivering their matches from the browser
+use super::alerts::{Alert, AlertConfig, Rule};
+use super::i18n::{Language, Zone};
+use super::types::MarketStatusType;
+use super::{cache, Msg, DAI_DECIMALS};
+
+use adex_domain::BigNum;
+use chrono::{TimeZone, Utc};
+use seed::prelude::*;
+use seed::{Method, Request};
+use web_sys::{Notification, NotificationOptions, NotificationPermission};
+
+const ALERTS_KEY: &str = "adex-explorer-alerts";
+// How many of the last matches are listed
+const MAX_FIRED: usize = 50;
+
+pub fn load() -> AlertConfig {
+    cache::read(ALERTS_KEY).unwrap_or_default()
+}
+
+pub fn save(config: &AlertConfig) {
+    cache::write(ALERTS_KEY, config);
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum AlertField {
+    Kind,
+    Creator,
+    Status,
+    Percent,
+    Amount,
+    Webhook,
+}
+
+// The inputs of the new rule form, as typed
+#[derive(Default)]
+pub struct AlertForm {
+    pub kind: String,
+    pub creator: String,
+    pub status: String,
+    pub percent: String,
+    // In DAI
+    pub amount: String,
+}
+
+impl AlertForm {
+    pub fn rule(&self) -> Option<Rule> {
+        match self.kind.as_str() {
+            "paid" => Some(Rule::PaidCrosses {
+                percent: self.percent.trim().parse().ok()?,
+            }),
+            "locked" => {
+                let amount = self.amount.trim().parse::<f64>().ok()?;
+                if amount.is_nan() || amount < 0.0 {
+                    return None;
+                }
+                // In hundredths of a DAI, which is as precise as we display it
+                let cents = BigNum::from((amount * 100.0).round() as u64);
+                Some(Rule::LockedDrops {
+                    amount: &cents * &BigNum::from(10_u64.pow(DAI_DECIMALS - 2)),
+                })
+            }
+            _ => Some(Rule::StatusBecomes {
+                creator: Some(self.creator.trim())
+                    .filter(|x| !x.is_empty())
+                    .map(str::to_string),
+                status: MarketStatusType::ALL
+                    .iter()
+                    .find(|status| format!("{:?}", status) == self.status)
+                    .cloned()
+                    .unwrap_or(MarketStatusType::Unhealthy),
+            }),
+        }
+    }
+}
+
+// Delivers new matches, and keeps them for the alerts page
+pub fn fire(
+    config: &AlertConfig,
+    fired: &mut Vec<Alert>,
+    alerts: Vec<Alert>,
+    orders: &mut impl Orders<Msg>,
+) {
+    for alert in alerts.iter() {
+        if config.notify {
+            notify(alert);
+        }
+        if let Some(webhook) = &config.webhook {
+            orders.perform_cmd(
+                Request::new(webhook.clone())
+                    .method(Method::Post)
+                    .body_json(alert)
+                    .fetch_string_data(Msg::AlertWebhookDelivered),
+            );
+        }
+    }
+    for alert in alerts.into_iter() {
+        fired.insert(0, alert);
+    }
+    fired.truncate(MAX_FIRED);
+}
+
+fn notify(alert: &Alert) {
+    if Notification::permission() != NotificationPermission::Granted {
+        return;
+    }
+    let mut options = NotificationOptions::new();
+    options.body(&alert.message);
+    if Notification::new_with_options("AdEx Explorer alert", &options).is_err() {
+        log!("Unable to show a notification:", &alert.message);
+    }
+}
+
+pub fn request_notification_permission() {
+    if Notification::request_permission().is_err() {
+        log!("Notifications are not supported");
+    }
+}
+
+pub fn alerts_page(
+    lang: Language,
+    config: &AlertConfig,
+    form: &AlertForm,
+    fired: &[Alert],
+    zone: Zone,
+) -> Node<Msg> {
+    let kind_option = |value: &str, label: &'static str| {
+        let label = lang.t(label);
+        if form.kind == value {
+            option![attrs! {At::Value => value; At::Selected => true}, label]
+        } else {
+            option![attrs! {At::Value => value}, label]
+        }
+    };
+    div![
+        class!["alerts"],
+        h2![lang.t("Alerts")],
+        table![
+            config.rules.iter().enumerate().map(|(i, rule)| tr![
+                td![rule.describe(lang)],
+                td![button![simple_ev(Ev::Click, Msg::RemoveAlertRule(i)), lang.t("Remove")]],
+            ]).collect::<Vec<_>>()
+        ],
+        div![
+            class!["alert-form"],
+            select![
+                kind_option("status", "A channel becomes..."),
+                kind_option("paid", "The paid % of a channel crosses..."),
+                kind_option("locked", "The locked on-chain balance drops by more than..."),
+                input_ev(Ev::Input, |x| Msg::AlertFormInput(AlertField::Kind, x))
+            ],
+            match form.kind.as_str() {
+                "paid" => vec![input![
+                    attrs! {At::Type => "number"; At::Placeholder => "%"; At::Value => &form.percent},
+                    input_ev(Ev::Input, |x| Msg::AlertFormInput(AlertField::Percent, x))
+                ]],
+                "locked" => vec![input![
+                    attrs! {At::Type => "number"; At::Placeholder => "DAI"; At::Value => &form.amount},
+                    input_ev(Ev::Input, |x| Msg::AlertFormInput(AlertField::Amount, x))
+                ]],
+                _ => vec![
+                    select![
+                        MarketStatusType::ALL
+                            .iter()
+                            .map(|status| {
+                                let name = format!("{:?}", status);
+                                let label = lang.t(status.name());
+                                let selected = form.status == name
+                                    || (form.status.is_empty() && *status == MarketStatusType::Unhealthy);
+                                if selected {
+                                    option![attrs! {At::Value => &name; At::Selected => true}, label]
+                                } else {
+                                    option![attrs! {At::Value => &name}, label]
+                                }
+                            })
+                            .collect::<Vec<_>>(),
+                        input_ev(Ev::Input, |x| Msg::AlertFormInput(AlertField::Status, x))
+                    ],
+                    input![
+                        attrs! {At::Placeholder => lang.t("Created by (any if empty)"); At::Value => &form.creator},
+                        input_ev(Ev::Input, |x| Msg::AlertFormInput(AlertField::Creator, x))
+                    ],
+                ],
+            },
+            button![simple_ev(Ev::Click, Msg::AddAlertRule), lang.t("Add")],
+        ],
+        h3![lang.t("Delivery")],
+        label![
+            input![
+                if config.notify {
+                    attrs! { At::Type => "checkbox"; At::Checked => true }
+                } else {
+                    attrs! { At::Type => "checkbox" }
+                },
+                simple_ev(Ev::Click, Msg::ToggleAlertNotifications)
+            ],
+            lang.t("Browser notifications")
+        ],
+        input![
+            attrs! {
+                At::Placeholder => lang.t("Webhook URL");
+                At::Value => config.webhook.clone().unwrap_or_default()
+            },
+            input_ev(Ev::Change, |x| Msg::AlertFormInput(AlertField::Webhook, x))
+        ],
+        h3![lang.t("Recent matches")],
+        if fired.is_empty() {
+            p![lang.t("None since the explorer was opened")]
+        } else {
+            table![fired
+                .iter()
+                .map(|alert| tr![
+                    td![lang.timestamp(zone, &Utc.timestamp(alert.time, 0))],
+                    td![alert.message.clone()],
+                ])
+                .collect::<Vec<_>>()]
+        },
+    ]
+}
@@ -0,0 +1,318 @@
+// The explorer in a terminal
+//
+//   adex-explorer [--format table|json|csv] summary
+//   adex-explorer [--format table|json|csv] channels [--all] [--sort deposit|status|created]
+//   adex-explorer [--format table|json|csv] channel <id>
+use adex_explorer::i18n::{plain, Language};
+use adex_explorer::native::{endpoints_from_env, fetch_json, fetch_market_data};
+use adex_explorer::refresh::Source;
+use adex_explorer::types::{ChannelSort, MarketChannel};
+use adex_explorer::{dai_readable, paid_percentage, report, CPM_PRECISION, DAI_DECIMALS};
+
+use adex_domain::BigNum;
+use serde::Serialize;
+use std::env;
+use std::process;
+
+const USAGE: &str = "Usage:
+  adex-explorer [--format table|json|csv] summary
+  adex-explorer [--format table|json|csv] channels [--all] [--sort deposit|status|created]
+  adex-explorer [--format table|json|csv] channel <id>";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+struct Args {
+    format: Format,
+    all: bool,
+    sort: ChannelSort,
+    positional: Vec<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args {
+        format: Format::Table,
+        all: false,
+        sort: ChannelSort::default(),
+        positional: vec![],
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                parsed.format = match args.next().as_ref().map(String::as_str) {
+                    Some("table") => Format::Table,
+                    Some("json") => Format::Json,
+                    Some("csv") => Format::Csv,
+                    _ => return Err("--format should be one of table, json or csv".to_string()),
+                }
+            }
+            "--all" => parsed.all = true,
+            "--sort" => {
+                parsed.sort = match args.next() {
+                    Some(sort) if ["deposit", "status", "created"].contains(&sort.as_str()) => {
+                        sort.into()
+                    }
+                    _ => return Err("--sort should be one of deposit, status or created".to_string()),
+                }
+            }
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ => parsed.positional.push(arg),
+        }
+    }
+    Ok(parsed)
+}
+
+// Rows of cells, printed as an aligned table or as CSV
+struct Table {
+    header: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    fn print(&self, format: Format) {
+        match format {
+            Format::Csv => {
+                println!("{}", csv_line(self.header.iter().map(|x| x.to_string())));
+                for row in self.rows.iter() {
+                    println!("{}", csv_line(row.iter().cloned()));
+                }
+            }
+            _ => {
+                let widths = self
+                    .header
+                    .iter()
+                    .enumerate()
+                    .map(|(i, title)| {
+                        self.rows
+                            .iter()
+                            .map(|row| row[i].chars().count())
+                            .fold(title.chars().count(), usize::max)
+                    })
+                    .collect::<Vec<_>>();
+                let line = |cells: Vec<String>| {
+                    cells
+                        .iter()
+                        .zip(widths.iter())
+                        .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                        .collect::<Vec<_>>()
+                        .join("  ")
+                        .trim_end()
+                        .to_string()
+                };
+                println!("{}", line(self.header.iter().map(|x| x.to_string()).collect()));
+                for row in self.rows.iter() {
+                    println!("{}", line(row.clone()));
+                }
+            }
+        }
+    }
+}
+
+fn csv_line(cells: impl Iterator<Item = String>) -> String {
+    cells
+        .map(|cell| {
+            if cell.contains(',') || cell.contains('"') || cell.contains('\n') {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), String> {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value).map_err(|e| e.to_string())?
+    );
+    Ok(())
+}
+
+fn fetch_channels() -> Result<Vec<MarketChannel>, String> {
+    fetch_json(&endpoints_from_env().source_url(Source::Channels))
+}
+
+// Rounded in tables, in full and without grouping in CSV, so that spreadsheets read them as
+// numbers; the unit is in the header, or in the label of the summary rows
+fn amount(format: Format, x: &BigNum, precision: u32) -> String {
+    match format {
+        Format::Csv => plain(x, DAI_DECIMALS),
+        _ => Language::English.fixed(x, DAI_DECIMALS, precision),
+    }
+}
+
+fn dai(format: Format, x: &BigNum) -> String {
+    amount(format, x, 2)
+}
+
+// At the prices of the price source, or the static ones
+fn usd(format: Format, x: &Option<BigNum>) -> String {
+    match x {
+        Some(x) => amount(format, x, 2),
+        None => "N/A".to_string(),
+    }
+}
+
+// CPM: the price of 1000 impressions
+fn cpm(format: Format, per_impression: &BigNum) -> String {
+    amount(format, &(per_impression * &1000.into()), CPM_PRECISION)
+}
+
+fn count(format: Format, x: &BigNum) -> String {
+    match format {
+        Format::Csv => plain(x, 0),
+        _ => Language::English.integer(x),
+    }
+}
+
+fn summary(format: Format) -> Result<(), String> {
+    let data = fetch_market_data(&endpoints_from_env())?;
+    let summary = report::summary(&data);
+    if format == Format::Json {
+        return print_json(&summary);
+    }
+    Table {
+        header: vec!["Card", "Value"],
+        rows: vec![
+            ("Campaigns", summary.summary.campaigns.to_string()),
+            ("Ad units", summary.summary.ad_units.to_string()),
+            ("Publishers", summary.summary.publishers.to_string()),
+            ("Advertisers", summary.summary.advertisers.to_string()),
+            ("Monthly impressions", count(format, &summary.monthly_impressions)),
+            ("Daily impressions", count(format, &summary.daily_impressions)),
+            ("Total campaign deposits (DAI)", dai(format, &summary.summary.total_deposit)),
+            ("Total campaign deposits (USD)", usd(format, &summary.total_deposit_usd)),
+            ("Paid out (DAI)", dai(format, &summary.summary.paid_out)),
+            ("Paid out (USD)", usd(format, &summary.paid_out_usd)),
+            ("Locked up on-chain (DAI)", dai(format, &summary.locked_on_chain)),
+            ("Locked up on-chain (USD)", usd(format, &summary.locked_on_chain_usd)),
+            ("24h volume (DAI)", dai(format, &summary.volume_24h)),
+            ("24h volume (USD)", usd(format, &summary.volume_24h_usd)),
+            ("Yearly impressions (transactions)", count(format, &summary.yearly_impressions)),
+        ]
+        .into_iter()
+        .map(|(label, value)| vec![label.to_string(), value])
+        .collect(),
+    }
+    .print(format);
+    Ok(())
+}
+
+fn channels(format: Format, all: bool, sort: ChannelSort) -> Result<(), String> {
+    let mut channels = fetch_channels()?
+        .into_iter()
+        .filter(|channel| all || !channel.status.status_type.is_finished())
+        .collect::<Vec<_>>();
+    channels.sort_by(|x, y| sort.compare(x, y));
+    if format == Format::Json {
+        return print_json(&channels);
+    }
+    Table {
+        header: vec![
+            "Id",
+            "Creator",
+            "Status",
+            "Deposit (DAI)",
+            "Paid (DAI)",
+            "Paid - %",
+            "CPM (DAI)",
+            "Created",
+        ],
+        rows: channels
+            .iter()
+            .map(|channel| {
+                let paid = channel.status.balances_sum();
+                vec![
+                    channel.id.clone(),
+                    channel.creator.clone(),
+                    format!("{:?}", channel.status.status_type),
+                    dai(format, &channel.deposit_amount),
+                    dai(format, &paid),
+                    paid_percentage(&paid, &channel.deposit_amount),
+                    if channel.spec.min_per_impression == channel.spec.max_per_impression {
+                        cpm(format, &channel.spec.min_per_impression)
+                    } else {
+                        format!(
+                            "{} - {}",
+                            cpm(format, &channel.spec.min_per_impression),
+                            cpm(format, &channel.spec.max_per_impression)
+                        )
+                    },
+                    channel.spec.created.format("%Y-%m-%d %H:%M").to_string(),
+                ]
+            })
+            .collect(),
+    }
+    .print(format);
+    Ok(())
+}
+
+fn channel(format: Format, id: &str) -> Result<(), String> {
+    let channel = fetch_channels()?
+        .into_iter()
+        .find(|channel| channel.id.eq_ignore_ascii_case(id))
+        .ok_or_else(|| format!("Channel {} not found", id))?;
+    if format == Format::Json {
+        return print_json(&channel);
+    }
+    let paid = channel.status.balances_sum();
+    if format == Format::Table {
+        println!("Channel {}", channel.id);
+        println!("Created by {}", channel.creator);
+        println!(
+            "{:?}, {} paid out of {} ({}), last checked {}",
+            channel.status.status_type,
+            dai_readable(&paid),
+            dai_readable(&channel.deposit_amount),
+            paid_percentage(&paid, &channel.deposit_amount),
+            channel.status.last_checked.format("%Y-%m-%d %H:%M UTC")
+        );
+        println!(
+            "Withdraw period from {}, valid until {}",
+            channel.spec.withdraw_period_start.format("%Y-%m-%d %H:%M UTC"),
+            channel.valid_until.format("%Y-%m-%d %H:%M UTC")
+        );
+        println!();
+    }
+    let mut balances = channel.status.balances.iter().collect::<Vec<_>>();
+    balances.sort_by(|(_, x), (_, y)| y.cmp(x));
+    Table {
+        header: vec!["Earner", "Balance (DAI)", "Share"],
+        rows: balances
+            .into_iter()
+            .map(|(earner, balance)| {
+                vec![
+                    earner.clone(),
+                    dai(format, balance),
+                    if paid == BigNum::from(0) {
+                        "N/A".to_string()
+                    } else {
+                        paid_percentage(balance, &paid)
+                    },
+                ]
+            })
+            .collect(),
+    }
+    .print(format);
+    Ok(())
+}
+
+fn main() {
+    let result = parse_args(env::args().skip(1)).and_then(|args| {
+        match args.positional.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+            ["summary"] => summary(args.format),
+            ["channels"] => channels(args.format, args.all, args.sort),
+            ["channel", id] => channel(args.format, id),
+            _ => Err(USAGE.to_string()),
+        }
+    });
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
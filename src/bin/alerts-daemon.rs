@@ -0,0 +1,87 @@
+// Watches the market with the same alert rules as the explorer, and delivers their matches to a webhook
+//
+//   alerts-daemon [config] [interval in seconds]
+//
+// The config is the JSON of the rules and the webhook, e.g.
+// {"rules": [{"type": "paidCrosses", "percent": 90}], "webhook": "http://127.0.0.1:8020/alerts"}
+use adex_explorer::alerts::{self, AlertConfig, MarketState};
+use adex_explorer::i18n::Language;
+use adex_explorer::native::{endpoints_from_env, fetch_json, post_json};
+use adex_explorer::refresh::Source;
+use adex_explorer::types::{EtherscanBalResp, MarketChannel};
+
+use chrono::Utc;
+use std::env;
+use std::fs;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+const DEFAULT_CONFIG: &str = "alerts.json";
+const DEFAULT_INTERVAL: u64 = 60;
+
+fn usage() -> ! {
+    eprintln!("Usage: alerts-daemon [config] [interval in seconds]");
+    process::exit(1);
+}
+
+fn load_config(path: &str) -> Result<AlertConfig, String> {
+    let json = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    serde_json::from_str(&json).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    let config_path = args.get(1).map(String::as_str).unwrap_or(DEFAULT_CONFIG);
+    let interval = match args.get(2) {
+        Some(interval) => interval.parse().unwrap_or_else(|_| usage()),
+        None => DEFAULT_INTERVAL,
+    };
+    let config = load_config(config_path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    let endpoints = endpoints_from_env();
+    for rule in config.rules.iter() {
+        println!("Watching: {}", rule.describe(Language::English));
+    }
+
+    let mut previous: Option<(Vec<MarketChannel>, EtherscanBalResp)> = None;
+    loop {
+        let fetched = fetch_json::<Vec<MarketChannel>>(&endpoints.source_url(Source::Channels))
+            .and_then(|channels| {
+                fetch_json::<EtherscanBalResp>(&endpoints.source_url(Source::Balance))
+                    .map(|balance| (channels, balance))
+            });
+        match fetched {
+            Ok(current) => {
+                if let Some((channels, balance)) = &previous {
+                    let matches = alerts::evaluate(
+                        Utc::now().timestamp(),
+                        Language::English,
+                        &config.rules,
+                        MarketState {
+                            channels,
+                            locked_on_chain: Some(&balance.result),
+                        },
+                        MarketState {
+                            channels: &current.0,
+                            locked_on_chain: Some(&current.1.result),
+                        },
+                    );
+                    for alert in matches.iter() {
+                        println!("{}", alert.message);
+                        if let Some(webhook) = &config.webhook {
+                            if let Err(e) = post_json(webhook, alert) {
+                                eprintln!("Unable to deliver the alert: {}", e);
+                            }
+                        }
+                    }
+                }
+                previous = Some(current);
+            }
+            Err(e) => eprintln!("Unable to fetch the market: {}", e),
+        }
+        thread::sleep(Duration::from_secs(interval));
+    }
+}
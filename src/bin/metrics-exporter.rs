@@ -0,0 +1,95 @@
+// Periodically computes the numbers of the summary, and exposes them on `/metrics` for Prometheus
+//
+//   metrics-exporter [port] [interval in seconds]
+use adex_explorer::native::{endpoints_from_env, fetch_market_data};
+use adex_explorer::prometheus;
+
+use std::env;
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tiny_http::{Header, Response, Server};
+
+const DEFAULT_PORT: u16 = 9898;
+const DEFAULT_INTERVAL: u64 = 60;
+
+#[derive(Default)]
+struct State {
+    // The metrics of the last successful update
+    metrics: Option<String>,
+    failed_updates: u64,
+}
+
+fn update_periodically(state: Arc<Mutex<State>>, interval: Duration) {
+    let endpoints = endpoints_from_env();
+    loop {
+        let result = fetch_market_data(&endpoints).map(|data| prometheus::metrics(&data));
+        {
+            let mut state = state.lock().expect("the state is never poisoned");
+            match result {
+                Ok(metrics) => state.metrics = Some(metrics),
+                Err(e) => {
+                    eprintln!("Unable to update the metrics: {}", e);
+                    state.failed_updates += 1;
+                }
+            }
+        }
+        thread::sleep(interval);
+    }
+}
+
+fn serve(state: Arc<Mutex<State>>, port: u16) -> Result<(), String> {
+    let server = Server::http(("0.0.0.0", port)).map_err(|e| e.to_string())?;
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"text/plain; version=0.0.4"[..])
+        .expect("valid header");
+    println!("Serving metrics on http://0.0.0.0:{}/metrics", port);
+
+    for request in server.incoming_requests() {
+        let response = if request.url() != "/metrics" {
+            Response::from_string("Not found").with_status_code(404)
+        } else {
+            let state = state.lock().expect("the state is never poisoned");
+            match &state.metrics {
+                Some(metrics) => Response::from_string(format!(
+                    "{}# HELP adex_failed_updates_total Updates which failed to fetch the data\n\
+                     # TYPE adex_failed_updates_total counter\n\
+                     adex_failed_updates_total {}\n",
+                    metrics, state.failed_updates
+                ))
+                .with_header(content_type.clone()),
+                None => Response::from_string("No data yet").with_status_code(503),
+            }
+        };
+        if let Err(e) = request.respond(response) {
+            eprintln!("Unable to respond: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn usage() -> ! {
+    eprintln!("Usage: metrics-exporter [port] [interval in seconds]");
+    process::exit(1);
+}
+
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    let port = match args.get(1) {
+        Some(port) => port.parse().unwrap_or_else(|_| usage()),
+        None => DEFAULT_PORT,
+    };
+    let interval = match args.get(2) {
+        Some(interval) => interval.parse().unwrap_or_else(|_| usage()),
+        None => DEFAULT_INTERVAL,
+    };
+
+    let state = Arc::new(Mutex::new(State::default()));
+    let updated = state.clone();
+    thread::spawn(move || update_periodically(updated, Duration::from_secs(interval)));
+
+    if let Err(e) = serve(state, port) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,123 @@
+// Records the responses of the market, analytics, Etherscan and price APIs to fixture files,
+// and replays them on localhost with the same paths and query strings
+//
+//   mock-backend record [dir]
+//   mock-backend replay [dir] [port]
+//   mock-backend webhook [port]
+//
+// The explorer can then be pointed at it with
+// `?market=http://127.0.0.1:8010&analytics=http://127.0.0.1:8010&etherscan=http://127.0.0.1:8010&prices=http://127.0.0.1:8010`
+// The webhook stand-in prints the alerts it receives, e.g. with `http://127.0.0.1:8020/alerts` as the webhook URL
+use adex_explorer::endpoints::path_and_query;
+use adex_explorer::native::{endpoints_from_env, fetch_text};
+use adex_explorer::refresh::Source;
+
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process;
+use tiny_http::{Header, Response, Server};
+
+const DEFAULT_DIR: &str = "fixtures/recorded";
+const DEFAULT_PORT: u16 = 8010;
+const DEFAULT_WEBHOOK_PORT: u16 = 8020;
+
+// e.g. `/campaigns?all` is stored in `campaigns-all.json`
+fn fixture_name(path_and_query: &str) -> String {
+    let name = path_and_query
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>();
+    format!("{}.json", name.trim_matches('-'))
+}
+
+fn record(dir: &Path) -> Result<(), String> {
+    let endpoints = endpoints_from_env();
+    fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    for source in Source::ALL.iter() {
+        let body = fetch_text(&endpoints.source_url(*source))?;
+        let path = dir.join(fixture_name(&path_and_query(*source)));
+        fs::write(&path, body).map_err(|e| format!("{}: {}", path.display(), e))?;
+        println!("{:?} recorded to {}", source, path.display());
+    }
+    Ok(())
+}
+
+fn replay(dir: &Path, port: u16) -> Result<(), String> {
+    let server = Server::http(("127.0.0.1", port)).map_err(|e| e.to_string())?;
+    let header = |name: &str, value: &str| {
+        Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("valid header")
+    };
+    println!("Replaying {} on http://127.0.0.1:{}", dir.display(), port);
+
+    for request in server.incoming_requests() {
+        let path = dir.join(fixture_name(request.url()));
+        let response = match fs::read(&path) {
+            Ok(body) => Response::from_data(body)
+                .with_header(header("Content-Type", "application/json")),
+            Err(_) => Response::from_string(format!("{} was not recorded", request.url()))
+                .with_status_code(404),
+        }
+        // The explorer is served from another port
+        .with_header(header("Access-Control-Allow-Origin", "*"));
+        println!("{} {} {}", request.method(), request.url(), response.status_code().0);
+        if let Err(e) = request.respond(response) {
+            eprintln!("Unable to respond: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn webhook(port: u16) -> Result<(), String> {
+    let server = Server::http(("127.0.0.1", port)).map_err(|e| e.to_string())?;
+    let header = |name: &str, value: &str| {
+        Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("valid header")
+    };
+    println!("Receiving webhooks on http://127.0.0.1:{}", port);
+
+    for mut request in server.incoming_requests() {
+        let mut body = String::new();
+        if let Err(e) = request.as_reader().read_to_string(&mut body) {
+            eprintln!("Unable to read the request: {}", e);
+        }
+        // Browsers check whether they can POST JSON with a preflight request first
+        if !body.is_empty() {
+            println!("{} {} {}", request.method(), request.url(), body);
+        }
+        let response = Response::from_string("")
+            .with_header(header("Access-Control-Allow-Origin", "*"))
+            .with_header(header("Access-Control-Allow-Methods", "POST"))
+            .with_header(header("Access-Control-Allow-Headers", "Content-Type"));
+        if let Err(e) = request.respond(response) {
+            eprintln!("Unable to respond: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    let dir = PathBuf::from(args.get(2).map(String::as_str).unwrap_or(DEFAULT_DIR));
+    let result = match args.get(1).map(String::as_str) {
+        Some("record") => record(&dir),
+        Some("replay") => match args.get(3).map(|port| port.parse()) {
+            Some(Ok(port)) => replay(&dir, port),
+            Some(Err(_)) => Err(format!("Invalid port: {}", args[3])),
+            None => replay(&dir, DEFAULT_PORT),
+        },
+        Some("webhook") => match args.get(2).map(|port| port.parse()) {
+            Some(Ok(port)) => webhook(port),
+            Some(Err(_)) => Err(format!("Invalid port: {}", args[2])),
+            None => webhook(DEFAULT_WEBHOOK_PORT),
+        },
+        _ => Err(
+            "Usage: mock-backend record [dir] | mock-backend replay [dir] [port] | mock-backend webhook [port]"
+                .to_string(),
+        ),
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
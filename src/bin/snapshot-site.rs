@@ -0,0 +1,39 @@
+// Fetches the same data as the explorer and writes a static report of it:
+// `index.html` with the cards, the campaigns and the ad unit stats, and `summary.json` with the raw numbers
+//
+//   snapshot-site [dir]
+use adex_explorer::native::{endpoints_from_env, fetch_market_data};
+use adex_explorer::report;
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+const DEFAULT_DIR: &str = "report";
+
+fn generate(dir: &Path) -> Result<(), String> {
+    let data = fetch_market_data(&endpoints_from_env())?;
+    let summary = serde_json::to_string_pretty(&report::summary(&data)).map_err(|e| e.to_string())?;
+
+    fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let files = [
+        ("index.html", report::report_html(&data)),
+        ("summary.json", summary),
+        ("styles.css", include_str!("../../styles.css").to_string()),
+    ];
+    for (name, contents) in files.iter() {
+        let path = dir.join(name);
+        fs::write(&path, contents).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    println!("Report written to {}", dir.display());
+    Ok(())
+}
+
+fn main() {
+    let dir = PathBuf::from(env::args().nth(1).unwrap_or_else(|| DEFAULT_DIR.to_string()));
+    if let Err(e) = generate(&dir) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,69 @@
+use super::types;
+
+use chrono::{TimeZone, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use types::Loadable;
+
+const CACHE_PREFIX: &str = "adex-explorer-cache-";
+
+// The last successful response of a source, and when it was fetched
+#[derive(Serialize, Deserialize)]
+struct Cached<T> {
+    time: i64,
+    data: T,
+}
+
+pub fn read<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let storage = seed::storage::get_storage()?;
+    let json = storage.get_item(key).ok()??;
+    serde_json::from_str(&json).ok()
+}
+
+// Writing may fail, e.g. when the storage quota is exceeded; the explorer works without it
+pub fn write<T: Serialize>(key: &str, data: &T) {
+    let storage = match seed::storage::get_storage() {
+        Some(storage) => storage,
+        None => return,
+    };
+    match serde_json::to_string(data) {
+        Ok(json) => {
+            if storage.set_item(key, &json).is_err() {
+                log!("Unable to write to local storage:", key);
+            }
+        }
+        Err(e) => log!("Unable to serialize:", key, e.to_string()),
+    }
+}
+
+// A cached time is rendered as a date, so a corrupted one is as good as nothing cached
+pub fn load<T: DeserializeOwned>(source: &str) -> Loadable<T> {
+    match read::<Cached<T>>(&format!("{}{}", CACHE_PREFIX, source)) {
+        Some(cached) if is_valid_time(cached.time) => Loadable::Stale(cached.data, cached.time),
+        _ => Loadable::Loading,
+    }
+}
+
+fn is_valid_time(time: i64) -> bool {
+    time >= 0 && Utc.timestamp_opt(time, 0).single().is_some()
+}
+
+pub fn store<T: Serialize>(source: &str, time: i64, data: &T) {
+    write(
+        &format!("{}{}", CACHE_PREFIX, source),
+        &Cached { time, data },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_out_of_range_times() {
+        assert!(is_valid_time(0));
+        assert!(is_valid_time(1_571_400_000));
+        assert!(!is_valid_time(-1));
+        assert!(!is_valid_time(i64::max_value()));
+    }
+}
@@ -0,0 +1,220 @@
+// What changed between two consecutive refreshes of the market channels
+use super::i18n::Language;
+use super::types::{MarketChannel, MarketStatusType};
+
+use adex_domain::BigNum;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChannelChange {
+    // Not in the previous refresh
+    pub is_new: bool,
+    // From the previous status to the current one
+    pub status: Option<(MarketStatusType, MarketStatusType)>,
+    pub paid_increase: Option<BigNum>,
+}
+
+impl ChannelChange {
+    pub fn describe(&self, lang: Language) -> String {
+        let mut parts = vec![];
+        if self.is_new {
+            parts.push(lang.t("new").to_string());
+        }
+        if let Some((from, to)) = &self.status {
+            parts.push(format!("{} → {}", lang.t(from.name()), lang.t(to.name())));
+        }
+        if let Some(increase) = &self.paid_increase {
+            parts.push(lang.tf("+{} paid", &[&lang.dai(increase)]));
+        }
+        parts.join(", ")
+    }
+}
+
+// The changes of many channels, added up
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChangesSummary {
+    pub new: usize,
+    pub transitions: usize,
+    // How many channels paid out more
+    pub paid: usize,
+    pub paid_increase: BigNum,
+}
+
+impl ChangesSummary {
+    pub fn describe(&self, lang: Language) -> String {
+        let mut parts = vec![];
+        if self.new > 0 {
+            parts.push(lang.tn("{} new channel", "{} new channels", self.new as i64));
+        }
+        if self.transitions > 0 {
+            parts.push(lang.tn("{} status change", "{} status changes", self.transitions as i64));
+        }
+        if self.paid > 0 {
+            parts.push(lang.tf("+{} paid", &[&lang.dai(&self.paid_increase)]));
+        }
+        parts.join(", ")
+    }
+}
+
+pub fn summarize<'a>(changes: impl Iterator<Item = &'a ChannelChange>) -> ChangesSummary {
+    changes.fold(ChangesSummary::default(), |mut summary, change| {
+        if change.is_new {
+            summary.new += 1;
+        }
+        if change.status.is_some() {
+            summary.transitions += 1;
+        }
+        if let Some(increase) = &change.paid_increase {
+            summary.paid += 1;
+            summary.paid_increase = &summary.paid_increase + increase;
+        }
+        summary
+    })
+}
+
+// The changed channels only, by id
+pub fn channel_changes<'a>(
+    previous: &[MarketChannel],
+    current: &'a [MarketChannel],
+) -> HashMap<&'a str, ChannelChange> {
+    let previous = previous
+        .iter()
+        .map(|channel| (channel.id.as_str(), channel))
+        .collect::<HashMap<_, _>>();
+
+    current
+        .iter()
+        .filter_map(|channel| {
+            let change = match previous.get(channel.id.as_str()) {
+                None => ChannelChange {
+                    is_new: true,
+                    ..Default::default()
+                },
+                Some(before) => {
+                    let paid_before = before.status.balances_sum();
+                    let paid = channel.status.balances_sum();
+                    ChannelChange {
+                        is_new: false,
+                        status: Some((
+                            before.status.status_type.clone(),
+                            channel.status.status_type.clone(),
+                        ))
+                        .filter(|(from, to)| from != to),
+                        paid_increase: if paid > paid_before {
+                            Some(&paid - &paid_before)
+                        } else {
+                            None
+                        },
+                    }
+                }
+            };
+            if change == ChannelChange::default() {
+                None
+            } else {
+                Some((channel.id.as_str(), change))
+            }
+        })
+        .collect()
+}
+
+// What an address earned across all channels, except for the ones it created
+pub fn earnings<'a>(channels: impl Iterator<Item = &'a MarketChannel>, address: &str) -> BigNum {
+    channels
+        .filter(|channel| !channel.creator.eq_ignore_ascii_case(address))
+        .flat_map(|channel| {
+            channel
+                .status
+                .balances
+                .iter()
+                .filter(|(earner, _)| earner.eq_ignore_ascii_case(address))
+                .map(|(_, amount)| amount)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_fixtures::{dai, fixture};
+
+    #[test]
+    fn unchanged_channels_are_dropped() {
+        let channels = fixture();
+        assert!(channel_changes(&channels, &channels).is_empty());
+        // Paying out less isn't a change we highlight
+        let mut previous = fixture();
+        let earner = previous[0].status.balances.keys().next().cloned().unwrap();
+        previous[0].status.balances.insert(earner, dai(1000));
+        assert!(channel_changes(&previous, &channels).is_empty());
+    }
+
+    #[test]
+    fn new_channels() {
+        let channels = fixture();
+        let changes = channel_changes(&channels[..2], &channels);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(
+            changes.get(channels[2].id.as_str()),
+            Some(&ChannelChange {
+                is_new: true,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn status_transitions_and_paid_increases() {
+        let previous = fixture();
+        let mut channels = fixture();
+        channels[1].status.status_type = MarketStatusType::Unhealthy;
+        let earner = channels[0].status.balances.keys().next().cloned().unwrap();
+        channels[0].status.balances.insert(earner, dai(450));
+        let changes = channel_changes(&previous, &channels);
+
+        assert_eq!(changes.len(), 2);
+        assert_eq!(
+            changes.get(channels[0].id.as_str()),
+            Some(&ChannelChange {
+                paid_increase: Some(dai(50)),
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            changes.get(channels[1].id.as_str()),
+            Some(&ChannelChange {
+                status: Some((MarketStatusType::Active, MarketStatusType::Unhealthy)),
+                ..Default::default()
+            })
+        );
+
+        let summary = summarize(changes.values());
+        assert_eq!(
+            summary,
+            ChangesSummary {
+                new: 0,
+                transitions: 1,
+                paid: 1,
+                paid_increase: dai(50),
+            }
+        );
+        assert_eq!(
+            summary.describe(Language::English),
+            "1 status change, +50.00 DAI paid"
+        );
+    }
+
+    #[test]
+    fn earnings_exclude_own_channels() {
+        let channels = fixture();
+        // Earned in two channels, with the address in different cases
+        assert_eq!(
+            earnings(channels.iter(), "0xd6e371526cdaee04cd8af225d42e37bc14688d9e"),
+            dai(800)
+        );
+        // Only earned in a channel it created
+        assert_eq!(
+            earnings(channels.iter(), "0x712e9a2f83c41b0b7d6e5f4a3b2c1d0e9f8a7b6c"),
+            BigNum::from(0)
+        );
+    }
+}
@@ -0,0 +1,118 @@
+// Demo mode: serves the bundled fixtures instead of the real endpoints, so that the explorer
+// works without network access; enabled with `?demo`, e.g. `?demo&latency=2000&failure_rate=0.3`
+use super::endpoints::query_param;
+use super::refresh::Source;
+
+use futures::Future;
+use wasm_bindgen_futures::JsFuture;
+
+const DEFAULT_LATENCY_MS: i32 = 300;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DemoConfig {
+    // How long each response takes
+    pub latency_ms: i32,
+    // The share of responses which fail, between 0 and 1
+    pub failure_rate: f64,
+}
+
+impl DemoConfig {
+    pub fn from_search(search: &str) -> Option<Self> {
+        query_param(search, "demo")?;
+        Some(DemoConfig {
+            latency_ms: query_param(search, "latency")
+                .and_then(|x| x.parse().ok())
+                .unwrap_or(DEFAULT_LATENCY_MS)
+                .max(0),
+            failure_rate: query_param(search, "failure_rate")
+                .and_then(|x| x.parse::<f64>().ok())
+                .unwrap_or(0.0)
+                .max(0.0)
+                .min(1.0),
+        })
+    }
+
+    // The fixture as a data URL, so that it goes through the same fetch and parsing as real responses;
+    // injected failures are served as malformed JSON
+    pub fn data_url(&self, source: Source) -> String {
+        let body = if js_sys::Math::random() < self.failure_rate {
+            "{"
+        } else {
+            fixture(source)
+        };
+        format!(
+            "data:application/json,{}",
+            String::from(js_sys::encode_uri_component(body))
+        )
+    }
+
+    pub fn delay(&self) -> impl Future<Item = (), Error = ()> {
+        let latency_ms = self.latency_ms;
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            if seed::window()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, latency_ms)
+                .is_err()
+            {
+                log!("Unable to delay the demo response");
+            }
+        });
+        JsFuture::from(promise).map(|_| ()).map_err(|_| ())
+    }
+}
+
+fn fixture(source: Source) -> &'static str {
+    match source {
+        Source::Balance => include_str!("../fixtures/etherscan-balance.json"),
+        Source::Channels => include_str!("../fixtures/market-campaigns.json"),
+        Source::Volume => include_str!("../fixtures/analytics-daily-volume.json"),
+        Source::Impressions => include_str!("../fixtures/analytics-monthly-impressions.json"),
+        Source::DailyImpressions => include_str!("../fixtures/analytics-daily-impressions.json"),
+        Source::YearlyImpressions => include_str!("../fixtures/analytics-yearly-impressions.json"),
+        Source::Prices => include_str!("../fixtures/coingecko-prices.json"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_with_demo() {
+        assert_eq!(DemoConfig::from_search(""), None);
+        assert_eq!(DemoConfig::from_search("?latency=1000"), None);
+        assert_eq!(
+            DemoConfig::from_search("?demo"),
+            Some(DemoConfig {
+                latency_ms: DEFAULT_LATENCY_MS,
+                failure_rate: 0.0,
+            })
+        );
+        assert_eq!(
+            DemoConfig::from_search("?demo&latency=2000&failure_rate=0.3"),
+            Some(DemoConfig {
+                latency_ms: 2000,
+                failure_rate: 0.3,
+            })
+        );
+    }
+
+    #[test]
+    fn latency_in_range() {
+        let latency = |search| DemoConfig::from_search(search).unwrap().latency_ms;
+        assert_eq!(latency("?demo&latency="), DEFAULT_LATENCY_MS);
+        assert_eq!(latency("?demo&latency=slow"), DEFAULT_LATENCY_MS);
+        assert_eq!(latency("?demo&latency=1.5"), DEFAULT_LATENCY_MS);
+        assert_eq!(latency("?demo&latency=99999999999"), DEFAULT_LATENCY_MS);
+        assert_eq!(latency("?demo&latency=-100"), 0);
+    }
+
+    #[test]
+    fn failure_rate_in_range() {
+        let failure_rate = |search| DemoConfig::from_search(search).map(|x| x.failure_rate);
+        assert_eq!(failure_rate("?demo&failure_rate=often"), Some(0.0));
+        assert_eq!(failure_rate("?demo&failure_rate=NaN"), Some(0.0));
+        assert_eq!(failure_rate("?demo&failure_rate=-0.5"), Some(0.0));
+        assert_eq!(failure_rate("?demo&failure_rate=2"), Some(1.0));
+        assert_eq!(failure_rate("?demo&failure_rate=inf"), Some(1.0));
+    }
+}
@@ -0,0 +1,169 @@
+// Where each source is fetched from; overridable with the query string,
+// e.g. `?market=http://127.0.0.1:8010&analytics=http://127.0.0.1:8010&etherscan=http://127.0.0.1:8010`
+// The prices can come from any CoinGecko-compatible API, e.g. `?prices=http://127.0.0.1:8010`
+use super::refresh::Source;
+
+const MARKET_URL: &str = "https://market.adex.network";
+const ANALYTICS_URL: &str = "https://tom.adex.network";
+const ETHERSCAN_URL: &str = "https://api.etherscan.io";
+const PRICES_URL: &str = "https://api.coingecko.com";
+const ETHERSCAN_API_KEY: &str = "CUSGAYGXI4G2EIYN1FKKACBUIQMN5BKR2B";
+pub const DAI_ADDR: &str = "0x6B175474E89094C44Da98b954EedeAC495271d0F";
+// Deposits made before the SAI to DAI migration
+pub const SAI_ADDR: &str = "0x89d24A6b4CcB1B6fAA2625fE562bDD9a23260359";
+pub const CORE_ADDR: &str = "0x333420fc6a897356e69b62417cd17ff012177d2b";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Endpoints {
+    pub market: String,
+    pub analytics: String,
+    pub etherscan: String,
+    pub prices: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Endpoints {
+            market: MARKET_URL.to_string(),
+            analytics: ANALYTICS_URL.to_string(),
+            etherscan: ETHERSCAN_URL.to_string(),
+            prices: PRICES_URL.to_string(),
+        }
+    }
+}
+
+impl Endpoints {
+    pub fn from_search(search: &str) -> Self {
+        let default = Endpoints::default();
+        let endpoint = |name: &str, default: String| {
+            query_param(search, name)
+                .map(percent_decode)
+                .filter(|url| !url.is_empty())
+                .map(|url| url.trim_end_matches('/').to_string())
+                .unwrap_or(default)
+        };
+        Endpoints {
+            market: endpoint("market", default.market),
+            analytics: endpoint("analytics", default.analytics),
+            etherscan: endpoint("etherscan", default.etherscan),
+            prices: endpoint("prices", default.prices),
+        }
+    }
+
+    pub fn source_url(&self, source: Source) -> String {
+        let base = match source {
+            Source::Channels => &self.market,
+            Source::Balance => &self.etherscan,
+            Source::Prices => &self.prices,
+            _ => &self.analytics,
+        };
+        format!("{}{}", base, path_and_query(source))
+    }
+}
+
+// The same for every endpoint, so that a mock backend can serve all of them
+pub fn path_and_query(source: Source) -> String {
+    match source {
+        // On-chain balances
+        Source::Balance => format!(
+            "/api?module=account&action=tokenbalance&contractAddress={}&address={}&tag=latest&apikey={}",
+            DAI_ADDR, CORE_ADDR, ETHERSCAN_API_KEY
+        ),
+        // Campaigns from the market
+        Source::Channels => "/campaigns?all".to_string(),
+        Source::Volume => "/analytics?metric=eventPayouts&timeframe=day".to_string(),
+        Source::Impressions => "/analytics?metric=eventCounts&timeframe=month".to_string(),
+        Source::DailyImpressions => "/analytics?metric=eventCounts&timeframe=day".to_string(),
+        Source::YearlyImpressions => "/analytics?metric=eventCounts&timeframe=year".to_string(),
+        // USD prices of the deposit assets
+        Source::Prices => format!(
+            "/api/v3/simple/token_price/ethereum?contract_addresses={},{}&vs_currencies=usd",
+            DAI_ADDR, SAI_ADDR
+        ),
+    }
+}
+
+// `name` in `?a=1&name=2`; `Some("")` for a flag without a value, such as `?demo`
+pub fn query_param<'a>(search: &'a str, name: &str) -> Option<&'a str> {
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .map(|param| {
+            let mut pair = param.splitn(2, '=');
+            (pair.next().unwrap_or(""), pair.next().unwrap_or(""))
+        })
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+// Query values as encoded by browsers, e.g. `http%3A%2F%2F127.0.0.1%3A8010`; invalid escapes are
+// kept as they are
+pub fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = match bytes[i] {
+            b'%' => value
+                .get(i + 1..i + 3)
+                .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        match (escaped, bytes[i]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (None, b'+') => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (None, byte) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_query_params() {
+        assert_eq!(query_param("?demo", "demo"), Some(""));
+        assert_eq!(query_param("?a=1&b=2", "b"), Some("2"));
+        assert_eq!(query_param("a=1&b=x=y", "b"), Some("x=y"));
+        assert_eq!(query_param("?a=1", "b"), None);
+        assert_eq!(query_param("", "a"), None);
+    }
+
+    #[test]
+    fn decodes_query_values() {
+        assert_eq!(percent_decode("http%3A%2F%2F127.0.0.1%3A8010"), "http://127.0.0.1:8010");
+        assert_eq!(percent_decode("a+b%20c"), "a b c");
+        // Malformed escapes are kept
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%+4%4"), "%zz%+4%4");
+    }
+
+    #[test]
+    fn overrides_endpoints() {
+        assert_eq!(Endpoints::from_search(""), Endpoints::default());
+        assert_eq!(Endpoints::from_search("?market=&demo"), Endpoints::default());
+        let endpoints = Endpoints::from_search(
+            "?market=http://127.0.0.1:8010/&analytics=http%3A%2F%2F127.0.0.1%3A8011&prices=http://p",
+        );
+        assert_eq!(endpoints.market, "http://127.0.0.1:8010");
+        assert_eq!(endpoints.analytics, "http://127.0.0.1:8011");
+        assert_eq!(endpoints.etherscan, ETHERSCAN_URL);
+        assert_eq!(endpoints.prices, "http://p");
+        assert_eq!(
+            endpoints.source_url(Source::Channels),
+            "http://127.0.0.1:8010/campaigns?all"
+        );
+    }
+}
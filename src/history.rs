@@ -0,0 +1,239 @@
+use super::{cache, i18n, stats, types, Msg};
+
+use adex_domain::BigNum;
+use chrono::{TimeZone, Utc};
+use i18n::{Compact, Language, Zone};
+use seed::prelude::*;
+use serde::{Deserialize, Serialize};
+use stats::Summary;
+use types::MarketChannel;
+
+const HISTORY_KEY: &str = "adex-explorer-history";
+// Record at most one snapshot per 5 minutes, and keep a week of them
+const HISTORY_INTERVAL: i64 = 300;
+const HISTORY_MAX_LEN: usize = 2016;
+
+// The values of the summary cards at a given time
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsSnapshot {
+    pub time: i64,
+    #[serde(flatten)]
+    pub summary: Summary,
+    pub locked_on_chain: Option<BigNum>,
+}
+
+impl MetricsSnapshot {
+    pub fn new<'a, I>(time: i64, channels: I, locked_on_chain: Option<BigNum>) -> Self
+    where
+        I: IntoIterator<Item = &'a MarketChannel>,
+        I::IntoIter: Clone,
+    {
+        MetricsSnapshot {
+            time,
+            summary: stats::summary(channels),
+            locked_on_chain,
+        }
+    }
+}
+
+pub fn load() -> Vec<MetricsSnapshot> {
+    cache::read(HISTORY_KEY).unwrap_or_default()
+}
+
+pub fn record(history: &mut Vec<MetricsSnapshot>, snapshot: MetricsSnapshot) {
+    if push(history, snapshot) {
+        cache::write(HISTORY_KEY, history);
+    }
+}
+
+// Whether the snapshot was kept, i.e. the last one is old enough
+fn push(history: &mut Vec<MetricsSnapshot>, snapshot: MetricsSnapshot) -> bool {
+    let is_recent = history
+        .last()
+        .map(|last| snapshot.time - last.time < HISTORY_INTERVAL)
+        .unwrap_or(false);
+    if is_recent {
+        return false;
+    }
+
+    history.push(snapshot);
+    if history.len() > HISTORY_MAX_LEN {
+        let excess = history.len() - HISTORY_MAX_LEN;
+        history.drain(..excess);
+    }
+    true
+}
+
+pub fn count_delta(lang: Language, current: usize, previous: usize) -> Option<String> {
+    match current as i64 - previous as i64 {
+        0 => None,
+        x if x > 0 => Some(lang.tf("{} since your last visit", &[&format!("+{}", lang.count(x))])),
+        x => Some(lang.tf("{} since your last visit", &[&format!("-{}", lang.count(-x))])),
+    }
+}
+
+pub fn dai_delta(lang: Language, current: &BigNum, previous: &BigNum) -> Option<String> {
+    if current > previous {
+        Some(lang.tf("{} since your last visit", &[&format!("+{}", lang.dai(&(current - previous)))]))
+    } else if current < previous {
+        Some(lang.tf("{} since your last visit", &[&format!("-{}", lang.dai(&(previous - current)))]))
+    } else {
+        None
+    }
+}
+
+pub fn history_charts(lang: Language, zone: Zone, history: &[MetricsSnapshot]) -> Node<Msg> {
+    if history.len() < 2 {
+        return seed::empty();
+    }
+    let last = &history[history.len() - 1];
+    // The first and the last snapshot, under every chart
+    let axis = (
+        lang.date_time(zone, &Utc.timestamp(history[0].time, 0)),
+        lang.date_time(zone, &Utc.timestamp(last.time, 0)),
+    );
+    div![
+        h2![lang.t("History")],
+        history_chart(
+            lang.t("Campaigns"),
+            lang.compact_count(&BigNum::from(last.summary.campaigns as u64)),
+            &history.iter().map(|x| x.summary.campaigns as f64).collect::<Vec<_>>(),
+            &axis
+        ),
+        history_chart(
+            lang.t("Publishers"),
+            lang.compact_count(&BigNum::from(last.summary.publishers as u64)),
+            &history.iter().map(|x| x.summary.publishers as f64).collect::<Vec<_>>(),
+            &axis
+        ),
+        history_chart(
+            lang.t("Total campaign deposits"),
+            lang.compact_dai(&last.summary.total_deposit),
+            &history.iter().filter_map(|x| dai(&x.summary.total_deposit)).collect::<Vec<_>>(),
+            &axis
+        ),
+        history_chart(
+            lang.t("Paid out"),
+            lang.compact_dai(&last.summary.paid_out),
+            &history.iter().filter_map(|x| dai(&x.summary.paid_out)).collect::<Vec<_>>(),
+            &axis
+        ),
+    ]
+}
+
+// Rounded to the cent; amounts which can't be converted are left out of the charts
+fn dai(x: &BigNum) -> Option<f64> {
+    Some(x.div_floor(&10_000_000_000_000_000u64.into()).to_f64()? / 100.0)
+}
+
+fn history_chart(
+    label: &str,
+    current: Compact,
+    values: &[f64],
+    axis: &(String, String),
+) -> Node<Msg> {
+    let min = values.iter().cloned().fold(std::f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(std::f64::NEG_INFINITY, f64::max);
+    let range = if max > min { max - min } else { 1.0 };
+    let width = 250_f64;
+    let height = 60_f64;
+    // Points may have been left out, down to a single one
+    let ratio = width / (values.len().max(2) - 1) as f64;
+    let points = values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            format!(
+                "{},{}",
+                (i as f64 * ratio).ceil(),
+                height - (v - min) / range * height
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    div![
+        class!["card chart"],
+        svg![
+            attrs! {
+                At::Style => "position: absolute; right: 0px; left: 0px; bottom: 10px;";
+                At::Width => format!("{}px", width);
+                At::Height => format!("{}px", height);
+                At::ViewBox => format!("0 0 {} {}", width, height);
+            },
+            polyline![attrs! {
+                At::Fill => "none";
+                At::Custom("stroke".into()) => "#c8dbec";
+                At::Custom("stroke-width".into()) => "4";
+                At::Custom("points".into()) => points
+            }],
+        ],
+        div![class!["card-value"], attrs! { At::Title => current.full }, current.short],
+        div![class!["card-label"], label],
+        div![
+            class!["chart-axis"],
+            span![axis.0.clone()],
+            span![axis.1.clone()]
+        ],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_fixtures::{dai, fixture};
+
+    fn snapshot(time: i64) -> MetricsSnapshot {
+        MetricsSnapshot::new(time, fixture().iter(), Some(dai(1000)))
+    }
+
+    #[test]
+    fn records_every_5_minutes() {
+        let mut history = vec![];
+        assert!(push(&mut history, snapshot(0)));
+        assert!(!push(&mut history, snapshot(HISTORY_INTERVAL - 1)));
+        assert!(push(&mut history, snapshot(HISTORY_INTERVAL)));
+        assert_eq!(history.iter().map(|x| x.time).collect::<Vec<_>>(), vec![0, HISTORY_INTERVAL]);
+        assert_eq!(history[1].summary.campaigns, 3);
+        assert_eq!(history[1].locked_on_chain, Some(dai(1000)));
+    }
+
+    #[test]
+    fn keeps_a_week() {
+        let mut history = vec![];
+        let first = snapshot(0);
+        for i in 0..=HISTORY_MAX_LEN as i64 {
+            push(
+                &mut history,
+                MetricsSnapshot {
+                    time: i * HISTORY_INTERVAL,
+                    ..first.clone()
+                },
+            );
+        }
+        assert_eq!(history.len(), HISTORY_MAX_LEN);
+        assert_eq!(history[0].time, HISTORY_INTERVAL);
+    }
+
+    #[test]
+    fn deltas_since_last_visit() {
+        let lang = Language::English;
+        assert_eq!(count_delta(lang, 5, 3), Some("+2 since your last visit".to_string()));
+        assert_eq!(count_delta(lang, 3, 5), Some("-2 since your last visit".to_string()));
+        assert_eq!(count_delta(lang, 3, 3), None);
+        assert_eq!(
+            dai_delta(lang, &dai(1500), &dai(1000)),
+            Some("+500.00 DAI since your last visit".to_string())
+        );
+        assert_eq!(
+            dai_delta(lang, &dai(1000), &dai(1500)),
+            Some("-500.00 DAI since your last visit".to_string())
+        );
+        assert_eq!(dai_delta(lang, &dai(1000), &dai(1000)), None);
+    }
+
+    #[test]
+    fn chart_values() {
+        assert_eq!(super::dai(&dai(1500)).map(|x| x.to_string()), Some("1500".to_string()));
+    }
+}
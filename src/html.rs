@@ -0,0 +1,54 @@
+// Renders a virtual DOM tree as HTML, outside of the browser: for the snapshot tests and the static report
+use super::Msg;
+
+use seed::prelude::*;
+
+// Elements which never have children or a closing tag
+const VOID_ELEMENTS: [&str; 6] = ["br", "hr", "img", "input", "link", "meta"];
+
+pub fn to_html(node: &Node<Msg>) -> String {
+    let mut out = String::new();
+    write_node(node, 0, &mut out);
+    out
+}
+
+fn write_node(node: &Node<Msg>, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    match node {
+        Node::Element(el) => {
+            let tag = el.tag.as_str();
+            out.push_str(&indent);
+            out.push('<');
+            out.push_str(tag);
+            // Attribute values may come from the market, e.g. the media URLs of the ad units
+            for (name, value) in el.attrs.vals.iter() {
+                out.push_str(&format!(" {}=\"{}\"", name.as_str(), escape(value)));
+            }
+            // Styles are only ever set by the views themselves
+            let style = el.style.to_string();
+            if !style.is_empty() {
+                out.push(' ');
+                out.push_str(&style);
+            }
+            out.push_str(">\n");
+            if VOID_ELEMENTS.contains(&tag) {
+                return;
+            }
+            for child in el.children.iter() {
+                write_node(child, depth + 1, out);
+            }
+            out.push_str(&format!("{}</{}>\n", indent, tag));
+        }
+        Node::Text(text) => out.push_str(&format!("{}{}\n", indent, escape(&text.text))),
+        Node::Empty => (),
+    }
+}
+
+// Safe both in text and in quoted attribute values
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
@@ -0,0 +1,829 @@
+// The languages of the explorer: message catalogs, and number, currency and date formatting
+// Messages are looked up by their English text, which is also the fallback
+// Dates are shown in local time or in UTC, and amounts are formatted from their digits, so that
+// they are never truncated
+use super::{CPM_PRECISION, DAI_DECIMALS};
+
+use adex_domain::BigNum;
+use chrono::{DateTime, FixedOffset, Utc};
+use num_format::{Locale, ToFormattedString};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Language {
+    English,
+    German,
+    Bulgarian,
+}
+
+impl Default for Language {
+    fn default() -> Self {
+        Language::English
+    }
+}
+
+// UTC by default, so that the static report and the snapshots don't depend on the machine
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Zone {
+    Local,
+    Utc,
+}
+
+impl Default for Zone {
+    fn default() -> Self {
+        Zone::Utc
+    }
+}
+
+impl Zone {
+    pub const ALL: [Zone; 2] = [Zone::Local, Zone::Utc];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Zone::Local => "local",
+            Zone::Utc => "utc",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Zone> {
+        Zone::ALL.iter().cloned().find(|zone| zone.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Zone::Local => "Local time",
+            Zone::Utc => "UTC",
+        }
+    }
+
+    fn offset(self, t: &DateTime<Utc>) -> FixedOffset {
+        match self {
+            Zone::Local => local_offset(t),
+            Zone::Utc => FixedOffset::east(0),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_offset(t: &DateTime<Utc>) -> FixedOffset {
+    // In minutes, positive west of UTC
+    let date = js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(t.timestamp_millis() as f64));
+    FixedOffset::west((date.get_timezone_offset() * 60.0) as i32)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn local_offset(t: &DateTime<Utc>) -> FixedOffset {
+    use chrono::TimeZone;
+    chrono::Local.offset_from_utc_datetime(&t.naive_utc())
+}
+
+// An amount in compact notation, and in full for the tooltip
+#[derive(Clone, Debug, PartialEq)]
+pub struct Compact {
+    pub short: String,
+    pub full: String,
+}
+
+const COMPACT_SUFFIXES: [&str; 4] = ["K", "M", "B", "T"];
+
+// BigNum only exposes its decimal digits through serde, as a string; anything else is a bug,
+// which must not be shown as a wrong amount
+fn digits(amount: &BigNum) -> String {
+    match serde_json::to_value(amount).expect("a BigNum should always serialize") {
+        serde_json::Value::String(digits) => digits,
+        other => panic!("a BigNum should serialize to its digits, not {}", other),
+    }
+}
+
+// `amount` / 10 ** `decimals` in full, without grouping, for machine-readable exports
+pub fn plain(amount: &BigNum, decimals: u32) -> String {
+    let (integer, fraction) = split_units(amount, decimals);
+    match fraction.trim_end_matches('0') {
+        "" => integer,
+        fraction => format!("{}.{}", integer, fraction),
+    }
+}
+
+// The integer and the fractional digits of `amount` / 10 ** `decimals`
+fn split_units(amount: &BigNum, decimals: u32) -> (String, String) {
+    let decimals = decimals as usize;
+    let padded = format!("{:0>width$}", digits(amount), width = decimals + 1);
+    let (integer, fraction) = padded.split_at(padded.len() - decimals);
+    (integer.to_string(), fraction.to_string())
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::German, Language::Bulgarian];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::Bulgarian => "bg",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Language> {
+        Language::ALL.iter().cloned().find(|lang| lang.code() == code)
+    }
+
+    // In the language itself, for the picker
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+            Language::Bulgarian => "Български",
+        }
+    }
+
+    pub fn locale(self) -> Locale {
+        match self {
+            Language::English => Locale::en,
+            Language::German => Locale::de,
+            Language::Bulgarian => Locale::bg,
+        }
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::English => &[],
+            Language::German => GERMAN,
+            Language::Bulgarian => BULGARIAN,
+        }
+    }
+
+    pub fn t(self, message: &'static str) -> &'static str {
+        self.catalog()
+            .iter()
+            .find(|(english, _)| *english == message)
+            .map(|(_, translated)| *translated)
+            .unwrap_or(message)
+    }
+
+    // Translates `message`, then fills its `{}` placeholders in order
+    pub fn tf(self, message: &'static str, args: &[&dyn Display]) -> String {
+        let mut parts = self.t(message).split("{}");
+        let mut result = parts.next().unwrap_or_default().to_string();
+        for (i, part) in parts.enumerate() {
+            if let Some(arg) = args.get(i) {
+                result.push_str(&arg.to_string());
+            }
+            result.push_str(part);
+        }
+        result
+    }
+
+    // Counted messages: the singular is for exactly one, which holds in all of our languages
+    pub fn tn(self, singular: &'static str, plural: &'static str, n: i64) -> String {
+        self.tf(if n == 1 { singular } else { plural }, &[&n])
+    }
+
+    pub fn count<N: ToFormattedString>(self, n: N) -> String {
+        n.to_formatted_string(&self.locale())
+    }
+
+    fn group(self, integer: &str) -> String {
+        let separator = self.locale().separator();
+        let mut grouped = String::new();
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                grouped.push_str(separator);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+
+    // With the digit grouping and the decimal separator of the locale
+    pub fn decimal(self, value: f64, precision: usize) -> String {
+        let locale = self.locale();
+        let fixed = format!("{:.*}", precision, value.abs());
+        let mut parts = fixed.splitn(2, '.');
+        let grouped = self.group(parts.next().unwrap_or_default());
+        let sign = if value < 0.0 && fixed.chars().any(|x| x.is_digit(10) && x != '0') {
+            locale.minus_sign()
+        } else {
+            ""
+        };
+        match parts.next() {
+            Some(fraction) => format!("{}{}{}{}", sign, grouped, locale.decimal(), fraction),
+            None => format!("{}{}", sign, grouped),
+        }
+    }
+
+    pub fn integer(self, n: &BigNum) -> String {
+        self.group(&digits(n))
+    }
+
+    // `amount` / 10 ** `decimals`, rounded down to `precision` decimals
+    pub fn fixed(self, amount: &BigNum, decimals: u32, precision: u32) -> String {
+        let (integer, fraction) = split_units(amount, decimals);
+        match precision.min(decimals) as usize {
+            0 => self.group(&integer),
+            precision => format!(
+                "{}{}{}",
+                self.group(&integer),
+                self.locale().decimal(),
+                &fraction[..precision]
+            ),
+        }
+    }
+
+    // `amount` / 10 ** `decimals`, with all of its significant decimals
+    pub fn full(self, amount: &BigNum, decimals: u32) -> String {
+        let (integer, fraction) = split_units(amount, decimals);
+        match fraction.trim_end_matches('0') {
+            "" => self.group(&integer),
+            fraction => format!("{}{}{}", self.group(&integer), self.locale().decimal(), fraction),
+        }
+    }
+
+    // 1.2K, 3.4M, 5.6B, 7.8T, rounded down; past that, in scientific notation
+    pub fn compact(self, amount: &BigNum, decimals: u32) -> String {
+        let (integer, _) = split_units(amount, decimals);
+        let magnitude = (integer.len() - 1) / 3;
+        if magnitude == 0 {
+            return self.fixed(amount, decimals, 2);
+        }
+        let decimal = self.locale().decimal();
+        match COMPACT_SUFFIXES.get(magnitude - 1) {
+            Some(suffix) => {
+                let lead = integer.len() - magnitude * 3;
+                match &integer[lead..=lead] {
+                    "0" => format!("{}{}", &integer[..lead], suffix),
+                    tenth => format!("{}{}{}{}", &integer[..lead], decimal, tenth, suffix),
+                }
+            }
+            None => format!("{}{}{}e{}", &integer[..1], decimal, &integer[1..2], integer.len() - 1),
+        }
+    }
+
+    pub fn compact_count(self, n: &BigNum) -> Compact {
+        Compact {
+            short: self.compact(n, 0),
+            full: self.integer(n),
+        }
+    }
+
+    pub fn compact_dai(self, amount: &BigNum) -> Compact {
+        Compact {
+            short: format!("{} DAI", self.compact(amount, DAI_DECIMALS)),
+            full: self.dai_full(amount),
+        }
+    }
+
+    pub fn dai(self, amount: &BigNum) -> String {
+        self.dai_precision(amount, 2)
+    }
+
+    pub fn dai_precision(self, amount: &BigNum, precision: u32) -> String {
+        format!("{} DAI", self.fixed(amount, DAI_DECIMALS, precision))
+    }
+
+    pub fn dai_full(self, amount: &BigNum) -> String {
+        format!("{} DAI", self.full(amount, DAI_DECIMALS))
+    }
+
+    // USD values are kept with as many decimals as DAI
+    pub fn compact_usd(self, amount: &BigNum) -> Compact {
+        Compact {
+            short: format!("{} USD", self.compact(amount, DAI_DECIMALS)),
+            full: self.usd_full(amount),
+        }
+    }
+
+    pub fn usd(self, amount: &BigNum) -> String {
+        format!("{} USD", self.fixed(amount, DAI_DECIMALS, 2))
+    }
+
+    pub fn usd_full(self, amount: &BigNum) -> String {
+        format!("{} USD", self.full(amount, DAI_DECIMALS))
+    }
+
+    // CPM: the price of 1000 impressions
+    pub fn cpm(self, per_impression: &BigNum) -> String {
+        self.dai_precision(&(per_impression * &1000.into()), CPM_PRECISION)
+    }
+
+    fn date_format(self) -> &'static str {
+        match self {
+            Language::English => "%Y-%m-%d",
+            Language::German | Language::Bulgarian => "%d.%m.%Y",
+        }
+    }
+
+    fn format_in(self, zone: Zone, t: &DateTime<Utc>, time_format: &str) -> String {
+        let format = format!("{}{}", self.date_format(), time_format);
+        let formatted = t.with_timezone(&zone.offset(t)).format(&format).to_string();
+        match zone {
+            Zone::Utc if !time_format.is_empty() => format!("{} UTC", formatted),
+            _ => formatted,
+        }
+    }
+
+    pub fn date(self, zone: Zone, t: &DateTime<Utc>) -> String {
+        self.format_in(zone, t, "")
+    }
+
+    pub fn date_time(self, zone: Zone, t: &DateTime<Utc>) -> String {
+        self.format_in(zone, t, " %H:%M")
+    }
+
+    // Down to the second, for the tooltips of relative times
+    pub fn timestamp(self, zone: Zone, t: &DateTime<Utc>) -> String {
+        self.format_in(zone, t, " %H:%M:%S")
+    }
+
+    pub fn time_ago(self, zone: Zone, now_seconds: i64, t: &DateTime<Utc>) -> String {
+        match now_seconds - t.timestamp() {
+            x if x < 0 => self.t("just now").to_string(),
+            x if x < 60 => self.tn("{} second ago", "{} seconds ago", x),
+            x if x < 3600 => self.tn("{} minute ago", "{} minutes ago", x / 60),
+            x if x < 86400 => self.tn("{} hour ago", "{} hours ago", x / 3600),
+            _ => self.date(zone, t),
+        }
+    }
+}
+
+static GERMAN: &[(&str, &str)] = &[
+    // Summary
+    ("Loading...", "Wird geladen..."),
+    ("Campaigns", "Kampagnen"),
+    ("Ad units", "Werbeeinheiten"),
+    ("Publishers", "Publisher"),
+    ("Advertisers", "Werbetreibende"),
+    ("Monthly impressions", "Monatliche Impressionen"),
+    ("Daily impressions", "Tägliche Impressionen"),
+    ("Total campaign deposits", "Kampagneneinlagen insgesamt"),
+    ("Paid out", "Ausgezahlt"),
+    ("Locked up on-chain", "On-Chain gesperrt"),
+    ("24h volume", "24h-Volumen"),
+    ("Yearly impressions (transactions)", "Jährliche Impressionen (Transaktionen)"),
+    ("updated {}", "aktualisiert {}"),
+    ("stale, as of {}", "veraltet, Stand {}"),
+    ("{} since your last visit", "{} seit Ihrem letzten Besuch"),
+    ("The market is unreachable, showing data as of {}", "Der Markt ist nicht erreichbar, Daten vom {}"),
+    ("The market is unreachable, and nothing was cached yet", "Der Markt ist nicht erreichbar, und es wurde noch nichts zwischengespeichert"),
+    ("History", "Verlauf"),
+    ("Go to platform", "Zur Plattform"),
+    ("Go to website", "Zur Website"),
+    // Refreshing
+    ("Refresh now", "Jetzt aktualisieren"),
+    ("Auto-refresh: fast", "Automatisch aktualisieren: schnell"),
+    ("Auto-refresh: normal", "Automatisch aktualisieren: normal"),
+    ("Auto-refresh: slow", "Automatisch aktualisieren: langsam"),
+    ("Auto-refresh: off", "Automatisch aktualisieren: aus"),
+    ("Outdated after 5 minutes", "Veraltet nach 5 Minuten"),
+    ("Outdated after 10 minutes", "Veraltet nach 10 Minuten"),
+    ("Outdated after 30 minutes", "Veraltet nach 30 Minuten"),
+    ("Outdated after 1 hour", "Veraltet nach 1 Stunde"),
+    ("Local time", "Ortszeit"),
+    ("UTC", "UTC"),
+    ("Amounts in DAI", "Beträge in DAI"),
+    ("Amounts in USD", "Beträge in USD"),
+    ("Amounts in DAI and USD", "Beträge in DAI und USD"),
+    // Channels
+    ("Campaigns with {} units", "Kampagnen mit {}-Einheiten"),
+    ("Sort by deposit", "Nach Einlage sortieren"),
+    ("Sort by status", "Nach Status sortieren"),
+    ("Sort by created", "Nach Erstellung sortieren"),
+    ("Entering withdraw period in the next {} days", "Auszahlungszeitraum beginnt in den nächsten {} Tagen"),
+    ("Entering withdraw period in the next {} day", "Auszahlungszeitraum beginnt am nächsten {} Tag"),
+    ("Most targeted tags", "Am häufigsten angesprochene Tags"),
+    ("Channel {} not found", "Kanal {} nicht gefunden"),
+    ("Changes since last refresh", "Änderungen seit der letzten Aktualisierung"),
+    ("None", "Keine"),
+    ("URL", "URL"),
+    ("USD estimate", "USD-Schätzung"),
+    ("Deposit", "Einlage"),
+    ("CPM", "CPM"),
+    ("Paid", "Bezahlt"),
+    ("Paid - %", "Bezahlt - %"),
+    ("Pacing", "Tempo"),
+    ("Projected exhaustion", "Voraussichtlich aufgebraucht"),
+    ("Max impressions", "Max. Impressionen"),
+    ("Status", "Status"),
+    ("Initializing", "Initialisierung"),
+    ("Waiting", "Wartend"),
+    ("Invalid", "Ungültig"),
+    ("Ready", "Bereit"),
+    ("Active", "Aktiv"),
+    ("Offline", "Offline"),
+    ("Disconnected", "Getrennt"),
+    ("Unhealthy", "Fehlerhaft"),
+    ("Withdraw", "Auszahlung"),
+    ("Expired", "Abgelaufen"),
+    ("Exhausted", "Ausgeschöpft"),
+    ("Under-pacing", "Zu langsam"),
+    ("On pace", "Im Plan"),
+    ("Over-pacing", "Zu schnell"),
+    ("Created", "Erstellt"),
+    ("Withdraw period", "Auszahlungszeitraum"),
+    ("Expires", "Läuft ab"),
+    ("Lifecycle", "Lebenszyklus"),
+    ("Preview", "Vorschau"),
+    ("N/A", "k. A."),
+    ("{}% spent, {}% of time elapsed", "{}% ausgegeben, {}% der Zeit vergangen"),
+    // Channel page
+    ("Channel {}", "Kanal {}"),
+    ("Created by {}", "Erstellt von {}"),
+    ("{}, {} paid out of {}", "{}, {} von {} ausgezahlt"),
+    ("Validator status", "Validator-Status"),
+    ("Status history", "Statusverlauf"),
+    ("No status changes observed yet", "Noch keine Statusänderungen beobachtet"),
+    ("{} (first seen)", "{} (zuerst gesehen)"),
+    ("for {}", "für {}"),
+    // Ad types
+    ("Ad Size", "Werbeformat"),
+    ("Units", "Einheiten"),
+    ("Weighted CPM", "Gewichteter CPM"),
+    ("Min CPM", "Min. CPM"),
+    ("Median min. CPM", "Median-Mindest-CPM"),
+    ("Max CPM", "Max. CPM"),
+    ("Active volume", "Aktives Volumen"),
+    ("Total volume", "Gesamtvolumen"),
+    ("Include inactive ad types", "Inaktive Werbeformate einbeziehen"),
+    // Time
+    ("just now", "gerade eben"),
+    ("{} seconds ago", "vor {} Sekunden"),
+    ("{} minutes ago", "vor {} Minuten"),
+    ("{} hours ago", "vor {} Stunden"),
+    ("passed", "vorbei"),
+    ("in {} seconds", "in {} Sekunden"),
+    ("in {} minutes", "in {} Minuten"),
+    ("in {} hours", "in {} Stunden"),
+    ("in {} days", "in {} Tagen"),
+    ("{} minutes", "{} Minuten"),
+    ("{} hours", "{} Stunden"),
+    ("{} days", "{} Tage"),
+    ("{} second ago", "vor {} Sekunde"),
+    ("{} minute ago", "vor {} Minute"),
+    ("{} hour ago", "vor {} Stunde"),
+    ("in {} second", "in {} Sekunde"),
+    ("in {} minute", "in {} Minute"),
+    ("in {} hour", "in {} Stunde"),
+    ("in {} day", "in {} Tag"),
+    ("Created {}, active from {}, withdraw period {}, expires {} ({})", "Erstellt {}, aktiv ab {}, Auszahlungszeitraum {}, läuft ab {} ({})"),
+    ("created", "erstellt"),
+    ("active", "aktiv"),
+    ("in the withdraw period", "im Auszahlungszeitraum"),
+    ("expired", "abgelaufen"),
+    ("{} minute", "{} Minute"),
+    ("{} hour", "{} Stunde"),
+    ("{} day", "{} Tag"),
+    // Changes
+    ("new", "neu"),
+    ("+{} paid", "+{} bezahlt"),
+    ("{} new channel", "{} neuer Kanal"),
+    ("{} new channels", "{} neue Kanäle"),
+    ("{} status change", "{} Statusänderung"),
+    ("{} status changes", "{} Statusänderungen"),
+    // Targeting
+    ("Tag", "Tag"),
+    ("Active campaigns", "Aktive Kampagnen"),
+    ("Total deposits", "Einlagen insgesamt"),
+    ("Scores", "Bewertungen"),
+    ("{} (score: {})", "{} (Bewertung: {})"),
+    ("No targeting", "Kein Targeting"),
+    ("Campaign targeting", "Kampagnen-Targeting"),
+    // Watchlist
+    ("Watchlist", "Beobachtungsliste"),
+    ("Add to the watchlist", "Zur Beobachtungsliste hinzufügen"),
+    ("Remove from the watchlist", "Von der Beobachtungsliste entfernen"),
+    (
+        "Star channels, advertisers and publishers (☆) to watch them here",
+        "Markieren Sie Kanäle, Werbetreibende und Publisher (☆), um sie hier zu beobachten",
+    ),
+    ("Channel", "Kanal"),
+    ("Channels", "Kanäle"),
+    ("Not on the market", "Nicht auf dem Markt"),
+    ("Advertiser", "Werbetreibender"),
+    ("Finished", "Beendet"),
+    ("Deposits", "Einlagen"),
+    ("Publisher", "Publisher"),
+    ("Earned", "Verdient"),
+    ("+{} earned", "+{} verdient"),
+    // Alerts
+    ("Alerts", "Alarme"),
+    ("Remove", "Entfernen"),
+    ("Add", "Hinzufügen"),
+    ("A channel becomes...", "Ein Kanal wird..."),
+    ("The paid % of a channel crosses...", "Der bezahlte Anteil eines Kanals überschreitet..."),
+    ("The locked on-chain balance drops by more than...", "Das On-Chain gesperrte Guthaben sinkt um mehr als..."),
+    ("Created by (any if empty)", "Erstellt von (beliebig, wenn leer)"),
+    ("A channel created by {} becomes {}", "Ein von {} erstellter Kanal wird {}"),
+    ("Any channel becomes {}", "Ein beliebiger Kanal wird {}"),
+    ("The paid % of a channel crosses {}%", "Der bezahlte Anteil eines Kanals überschreitet {}%"),
+    ("The locked on-chain balance drops by more than {}", "Das On-Chain gesperrte Guthaben sinkt um mehr als {}"),
+    ("Delivery", "Zustellung"),
+    ("Browser notifications", "Browser-Benachrichtigungen"),
+    ("Webhook URL", "Webhook-URL"),
+    ("Recent matches", "Letzte Treffer"),
+    ("None since the explorer was opened", "Keine, seit der Explorer geöffnet wurde"),
+    ("Channel {} went from {} to {}", "Kanal {} wechselte von {} zu {}"),
+    ("Channel {} has paid out {} of its deposit", "Kanal {} hat {} seiner Einlage ausgezahlt"),
+    ("The locked on-chain balance dropped by {}, to {}", "Das On-Chain gesperrte Guthaben sank um {} auf {}"),
+];
+
+static BULGARIAN: &[(&str, &str)] = &[
+    // Summary
+    ("Loading...", "Зареждане..."),
+    ("Campaigns", "Кампании"),
+    ("Ad units", "Рекламни единици"),
+    ("Publishers", "Издатели"),
+    ("Advertisers", "Рекламодатели"),
+    ("Monthly impressions", "Месечни импресии"),
+    ("Daily impressions", "Дневни импресии"),
+    ("Total campaign deposits", "Общо депозити на кампании"),
+    ("Paid out", "Изплатени"),
+    ("Locked up on-chain", "Заключени във веригата"),
+    ("24h volume", "Обем за 24 ч."),
+    ("Yearly impressions (transactions)", "Годишни импресии (транзакции)"),
+    ("updated {}", "обновено {}"),
+    ("stale, as of {}", "остаряло, към {}"),
+    ("{} since your last visit", "{} от последното ви посещение"),
+    ("The market is unreachable, showing data as of {}", "Пазарът е недостъпен, показани са данни към {}"),
+    ("The market is unreachable, and nothing was cached yet", "Пазарът е недостъпен и все още няма запазени данни"),
+    ("History", "История"),
+    ("Go to platform", "Към платформата"),
+    ("Go to website", "Към сайта"),
+    // Refreshing
+    ("Refresh now", "Обнови сега"),
+    ("Auto-refresh: fast", "Автоматично обновяване: бързо"),
+    ("Auto-refresh: normal", "Автоматично обновяване: нормално"),
+    ("Auto-refresh: slow", "Автоматично обновяване: бавно"),
+    ("Auto-refresh: off", "Автоматично обновяване: изключено"),
+    ("Outdated after 5 minutes", "Остаряло след 5 минути"),
+    ("Outdated after 10 minutes", "Остаряло след 10 минути"),
+    ("Outdated after 30 minutes", "Остаряло след 30 минути"),
+    ("Outdated after 1 hour", "Остаряло след 1 час"),
+    ("Local time", "Местно време"),
+    ("UTC", "UTC"),
+    ("Amounts in DAI", "Суми в DAI"),
+    ("Amounts in USD", "Суми в USD"),
+    ("Amounts in DAI and USD", "Суми в DAI и USD"),
+    // Channels
+    ("Campaigns with {} units", "Кампании с единици {}"),
+    ("Sort by deposit", "Сортирай по депозит"),
+    ("Sort by status", "Сортирай по статус"),
+    ("Sort by created", "Сортирай по създаване"),
+    ("Entering withdraw period in the next {} days", "Влизат в период на теглене през следващите {} дни"),
+    ("Entering withdraw period in the next {} day", "Влизат в период на теглене през следващия {} ден"),
+    ("Most targeted tags", "Най-таргетирани тагове"),
+    ("Channel {} not found", "Каналът {} не е намерен"),
+    ("Changes since last refresh", "Промени от последното обновяване"),
+    ("None", "Няма"),
+    ("URL", "URL"),
+    ("USD estimate", "Оценка в USD"),
+    ("Deposit", "Депозит"),
+    ("CPM", "CPM"),
+    ("Paid", "Платени"),
+    ("Paid - %", "Платени - %"),
+    ("Pacing", "Темпо"),
+    ("Projected exhaustion", "Очаквано изчерпване"),
+    ("Max impressions", "Макс. импресии"),
+    ("Status", "Статус"),
+    ("Initializing", "Инициализиране"),
+    ("Waiting", "Изчакване"),
+    ("Invalid", "Невалиден"),
+    ("Ready", "Готов"),
+    ("Active", "Активен"),
+    ("Offline", "Офлайн"),
+    ("Disconnected", "Прекъснат"),
+    ("Unhealthy", "Нездрав"),
+    ("Withdraw", "Теглене"),
+    ("Expired", "Изтекъл"),
+    ("Exhausted", "Изчерпан"),
+    ("Under-pacing", "Изостава"),
+    ("On pace", "В график"),
+    ("Over-pacing", "Изпреварва"),
+    ("Created", "Създадена"),
+    ("Withdraw period", "Период на теглене"),
+    ("Expires", "Изтича"),
+    ("Lifecycle", "Жизнен цикъл"),
+    ("Preview", "Преглед"),
+    ("N/A", "Н/П"),
+    ("{}% spent, {}% of time elapsed", "{}% изразходвани, изминали {}% от времето"),
+    // Channel page
+    ("Channel {}", "Канал {}"),
+    ("Created by {}", "Създаден от {}"),
+    ("{}, {} paid out of {}", "{}, изплатени {} от {}"),
+    ("Validator status", "Статус при валидатора"),
+    ("Status history", "История на статуса"),
+    ("No status changes observed yet", "Все още няма наблюдавани промени в статуса"),
+    ("{} (first seen)", "{} (за първи път)"),
+    ("for {}", "за {}"),
+    // Ad types
+    ("Ad Size", "Размер"),
+    ("Units", "Единици"),
+    ("Weighted CPM", "Претеглен CPM"),
+    ("Min CPM", "Мин. CPM"),
+    ("Median min. CPM", "Медианен мин. CPM"),
+    ("Max CPM", "Макс. CPM"),
+    ("Active volume", "Активен обем"),
+    ("Total volume", "Общ обем"),
+    ("Include inactive ad types", "Включи неактивните видове реклами"),
+    // Time
+    ("just now", "току-що"),
+    ("{} seconds ago", "преди {} секунди"),
+    ("{} minutes ago", "преди {} минути"),
+    ("{} hours ago", "преди {} часа"),
+    ("passed", "изминал"),
+    ("in {} seconds", "след {} секунди"),
+    ("in {} minutes", "след {} минути"),
+    ("in {} hours", "след {} часа"),
+    ("in {} days", "след {} дни"),
+    ("{} minutes", "{} минути"),
+    ("{} hours", "{} часа"),
+    ("{} days", "{} дни"),
+    ("{} second ago", "преди {} секунда"),
+    ("{} minute ago", "преди {} минута"),
+    ("{} hour ago", "преди {} час"),
+    ("in {} second", "след {} секунда"),
+    ("in {} minute", "след {} минута"),
+    ("in {} hour", "след {} час"),
+    ("in {} day", "след {} ден"),
+    ("Created {}, active from {}, withdraw period {}, expires {} ({})", "Създаден {}, активен от {}, период на теглене {}, изтича {} ({})"),
+    ("created", "създаден"),
+    ("active", "активен"),
+    ("in the withdraw period", "в периода на теглене"),
+    ("expired", "изтекъл"),
+    ("{} minute", "{} минута"),
+    ("{} hour", "{} час"),
+    ("{} day", "{} ден"),
+    // Changes
+    ("new", "нов"),
+    ("+{} paid", "+{} платени"),
+    ("{} new channel", "{} нов канал"),
+    ("{} new channels", "{} нови канала"),
+    ("{} status change", "{} промяна на статуса"),
+    ("{} status changes", "{} промени на статуса"),
+    // Targeting
+    ("Tag", "Таг"),
+    ("Active campaigns", "Активни кампании"),
+    ("Total deposits", "Общо депозити"),
+    ("Scores", "Оценки"),
+    ("{} (score: {})", "{} (оценка: {})"),
+    ("No targeting", "Без таргетиране"),
+    ("Campaign targeting", "Таргетиране на кампанията"),
+    // Watchlist
+    ("Watchlist", "Списък за наблюдение"),
+    ("Add to the watchlist", "Добави към списъка за наблюдение"),
+    ("Remove from the watchlist", "Премахни от списъка за наблюдение"),
+    (
+        "Star channels, advertisers and publishers (☆) to watch them here",
+        "Отбележете канали, рекламодатели и издатели (☆), за да ги наблюдавате тук",
+    ),
+    ("Channel", "Канал"),
+    ("Channels", "Канали"),
+    ("Not on the market", "Не е на пазара"),
+    ("Advertiser", "Рекламодател"),
+    ("Finished", "Приключени"),
+    ("Deposits", "Депозити"),
+    ("Publisher", "Издател"),
+    ("Earned", "Спечелени"),
+    ("+{} earned", "+{} спечелени"),
+    // Alerts
+    ("Alerts", "Известия"),
+    ("Remove", "Премахни"),
+    ("Add", "Добави"),
+    ("A channel becomes...", "Канал стане..."),
+    ("The paid % of a channel crosses...", "Платеният % на канал надхвърли..."),
+    ("The locked on-chain balance drops by more than...", "Заключеният във веригата баланс спадне с повече от..."),
+    ("Created by (any if empty)", "Създаден от (всеки, ако е празно)"),
+    ("A channel created by {} becomes {}", "Канал, създаден от {}, стане {}"),
+    ("Any channel becomes {}", "Който и да е канал стане {}"),
+    ("The paid % of a channel crosses {}%", "Платеният % на канал надхвърли {}%"),
+    ("The locked on-chain balance drops by more than {}", "Заключеният във веригата баланс спадне с повече от {}"),
+    ("Delivery", "Доставяне"),
+    ("Browser notifications", "Известия в браузъра"),
+    ("Webhook URL", "Webhook URL"),
+    ("Recent matches", "Последни съвпадения"),
+    ("None since the explorer was opened", "Няма, откакто експлорърът е отворен"),
+    ("Channel {} went from {} to {}", "Канал {} премина от {} към {}"),
+    ("Channel {} has paid out {} of its deposit", "Канал {} изплати {} от депозита си"),
+    ("The locked on-chain balance dropped by {}, to {}", "Заключеният във веригата баланс спадна с {} до {}"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn catalogs_keep_the_placeholders() {
+        for catalog in [GERMAN, BULGARIAN].iter() {
+            for (english, translated) in catalog.iter() {
+                assert_eq!(
+                    english.matches("{}").count(),
+                    translated.matches("{}").count(),
+                    "{}",
+                    english
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn statuses_are_translated() {
+        use super::super::pacing::PacingStatus;
+        use super::super::types::MarketStatusType;
+
+        let names = MarketStatusType::ALL
+            .iter()
+            .map(|status| status.name())
+            .chain(
+                [PacingStatus::UnderPacing, PacingStatus::OnPace, PacingStatus::OverPacing]
+                    .iter()
+                    .map(|status| status.name()),
+            );
+        for name in names {
+            assert!(GERMAN.iter().any(|(english, _)| *english == name), "{}", name);
+            assert!(BULGARIAN.iter().any(|(english, _)| *english == name), "{}", name);
+        }
+        assert_eq!(Language::German.t(MarketStatusType::Exhausted.name()), "Ausgeschöpft");
+    }
+
+    #[test]
+    fn falls_back_to_english() {
+        assert_eq!(Language::German.t("Campaigns"), "Kampagnen");
+        assert_eq!(Language::German.t("Not translated"), "Not translated");
+        assert_eq!(Language::English.tf("{} minutes ago", &[&5]), "5 minutes ago");
+        assert_eq!(Language::Bulgarian.tf("in {} days", &[&2]), "след 2 дни");
+    }
+
+    #[test]
+    fn singular_for_one() {
+        let days = |lang: Language, n| lang.tn("in {} day", "in {} days", n);
+        assert_eq!(days(Language::English, 1), "in 1 day");
+        assert_eq!(days(Language::English, 0), "in 0 days");
+        assert_eq!(days(Language::German, 1), "in 1 Tag");
+        assert_eq!(days(Language::Bulgarian, 1), "след 1 ден");
+        assert_eq!(days(Language::Bulgarian, 21), "след 21 дни");
+    }
+
+    #[test]
+    fn formats_dates() {
+        let t = Utc.ymd(2019, 10, 18).and_hms(12, 30, 15);
+        assert_eq!(Language::English.date_time(Zone::Utc, &t), "2019-10-18 12:30 UTC");
+        assert_eq!(Language::German.timestamp(Zone::Utc, &t), "18.10.2019 12:30:15 UTC");
+        assert_eq!(Language::English.date(Zone::Utc, &t), "2019-10-18");
+        assert_eq!(Language::English.time_ago(Zone::Utc, t.timestamp() + 90, &t), "1 minute ago");
+        assert_eq!(Language::English.time_ago(Zone::Utc, t.timestamp() + 150, &t), "2 minutes ago");
+        assert_eq!(Language::Bulgarian.time_ago(Zone::Utc, t.timestamp() + 3600, &t), "преди 1 час");
+    }
+
+    #[test]
+    fn formats_numbers() {
+        let dai = |x: u64| &BigNum::from(x) * &BigNum::from(10_000_000_000_000_000u64);
+        assert_eq!(Language::English.dai(&dai(123_456_789)), "1,234,567.89 DAI");
+        assert_eq!(Language::German.dai(&dai(123_456_789)), "1.234.567,89 DAI");
+        assert_eq!(Language::English.decimal(-0.001, 2), "0.00");
+        assert_eq!(Language::English.decimal(999.0, 0), "999");
+        assert_eq!(Language::German.count(1_234_567_u64), "1.234.567");
+        assert_eq!(Language::German.usd(&dai(123_456_789)), "1.234.567,89 USD");
+        assert_eq!(Language::English.compact_usd(&dai(123_456_789)).short, "1.2M USD");
+    }
+
+    #[test]
+    fn never_truncates() {
+        // Past both u64 and f64 precision
+        let huge = &BigNum::from(u64::max_value()) * &BigNum::from(1_000_000_000_000u64);
+        assert_eq!(
+            Language::English.integer(&huge),
+            "18,446,744,073,709,551,615,000,000,000,000"
+        );
+        assert_eq!(Language::English.compact(&huge, 0), "1.8e31");
+        let dai = Language::English.compact_dai(&huge);
+        assert_eq!(dai.short, "18.4T DAI");
+        assert_eq!(dai.full, "18,446,744,073,709.551615 DAI");
+        assert_eq!(Language::English.dai(&huge), "18,446,744,073,709.55 DAI");
+    }
+
+    #[test]
+    fn compact_notation() {
+        let compact = |n: u64| Language::English.compact(&BigNum::from(n), 0);
+        assert_eq!(compact(999), "999");
+        assert_eq!(compact(1_000), "1K");
+        assert_eq!(compact(1_299), "1.2K");
+        assert_eq!(compact(3_450_000), "3.4M");
+        assert_eq!(compact(5_600_000_000), "5.6B");
+        assert_eq!(compact(999_999_999_999_999), "999.9T");
+        assert_eq!(Language::German.compact(&BigNum::from(1_234_567), 0), "1,2M");
+        // Amounts below 1000 keep two decimals
+        let cents = &BigNum::from(12_345) * &BigNum::from(10_000_000_000_000_000u64);
+        assert_eq!(Language::English.compact(&cents, 18), "123.45");
+        assert_eq!(Language::English.full(&cents, 18), "123.45");
+        assert_eq!(Language::English.fixed(&cents, 18, 0), "123");
+    }
+
+    #[test]
+    fn plain_for_exports() {
+        let huge = &BigNum::from(u64::max_value()) * &BigNum::from(1_000_000_000_000u64);
+        assert_eq!(plain(&huge, 18), "18446744073709.551615");
+        assert_eq!(plain(&BigNum::from(5), 18), "0.000000000000000005");
+        assert_eq!(plain(&BigNum::from(0), 18), "0");
+        assert_eq!(plain(&BigNum::from(1_234_000), 3), "1234");
+    }
+}
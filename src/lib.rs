@@ -1,34 +1,77 @@
 #[macro_use]
 extern crate seed;
 
+pub mod alerts;
+mod alerts_page;
+mod cache;
+mod changes;
+mod demo;
+pub mod endpoints;
+mod history;
+#[cfg(not(target_arch = "wasm32"))]
+mod html;
+pub mod i18n;
+mod lifecycle;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod native;
+mod pacing;
+pub mod prices;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod prometheus;
+pub mod refresh;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod report;
+#[cfg(test)]
+mod snapshots;
+pub mod stats;
 mod stats_table;
-mod types;
+mod status_history;
+mod targeting;
+#[cfg(test)]
+mod test_fixtures;
+pub mod types;
+mod watchlist;
 
 use adex_domain::{AdUnit, BigNum, Channel};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeZone, Utc};
+use demo::DemoConfig;
+use endpoints::{Endpoints, CORE_ADDR, DAI_ADDR};
+use futures::Future;
+use i18n::{Compact, Language, Zone};
 use lazysort::*;
-use num_format::{Locale, ToFormattedString};
+use prices::{Currency, Valuation};
 use seed::fetch;
 use seed::prelude::*;
 use seed::{Method, Request};
+use refresh::{InFlight, RefreshRate, RequestId, Source};
 use stats_table::ad_unit_stats_table;
-use std::collections::HashSet;
-use types::{ChannelSort, EtherscanBalResp, Loadable, MarketChannel, AnalyticsResp};
+use types::{AdTypeSort, ChannelSort, EtherscanBalResp, Loadable, MarketChannel, AnalyticsResp, PricesResp};
+use std::collections::HashMap;
 
 use Loadable::*;
 
-const MARKET_URL: &str = "https://market.adex.network";
-const DAILY_VOL_URL: &str = "https://tom.adex.network/analytics?metric=eventPayouts&timeframe=day";
-const DAILY_IMPRESSIONS_URL: &str = "https://tom.adex.network/analytics?metric=eventCounts&timeframe=day";
-const IMPRESSIONS_URL: &str = "https://tom.adex.network/analytics?metric=eventCounts&timeframe=month";
-const YEARLY_IMPRESSIONS_URL: &str = "https://tom.adex.network/analytics?metric=eventCounts&timeframe=year";
-const ETHERSCAN_URL: &str = "https://api.etherscan.io/api";
-const ETHERSCAN_API_KEY: &str = "CUSGAYGXI4G2EIYN1FKKACBUIQMN5BKR2B";
 const IPFS_GATEWAY: &str = "https://ipfs.adex.network/ipfs/";
-const DAI_ADDR: &str = "0x6B175474E89094C44Da98b954EedeAC495271d0F";
-const CORE_ADDR: &str = "0x333420fc6a897356e69b62417cd17ff012177d2b";
 const DEFAULT_EARNER: &str = "0xb7d3f81e857692d13e9d63b232a90f4a1793189e";
-const REFRESH_MS: i32 = 30000;
+// How often we check whether any source is due for a refresh
+const TICK_MS: i32 = 5000;
+const REFRESH_RATE_KEY: &str = "adex-explorer-refresh-rate";
+const LANGUAGE_KEY: &str = "adex-explorer-language";
+const ZONE_KEY: &str = "adex-explorer-timezone";
+const CURRENCY_KEY: &str = "adex-explorer-currency";
+// Data older than this is flagged, both in cards and in the channel table
+const DEFAULT_STALENESS_THRESHOLD: i64 = 600;
+const STALENESS_THRESHOLD_KEY: &str = "adex-explorer-staleness-threshold";
+const DEFAULT_EXPIRING_DAYS: i64 = 7;
+pub const DAI_DECIMALS: u32 = 18;
+pub const CPM_PRECISION: u32 = 4;
+// Keys for caching the last successful response of each source
+const CHANNELS_CACHE: &str = "market-channels";
+const BALANCE_CACHE: &str = "balance";
+const VOLUME_CACHE: &str = "volume";
+const IMPRESSIONS_CACHE: &str = "impressions";
+const DAILY_IMPRESSIONS_CACHE: &str = "daily-impressions";
+const YEARLY_IMPRESSIONS_CACHE: &str = "yearly-impressions";
+const PRICES_CACHE: &str = "prices";
 
 // @TODO can we derive this automatically
 impl From<String> for ChannelSort {
@@ -46,16 +89,48 @@ impl From<String> for ChannelSort {
 pub struct Model {
     pub load_action: ActionLoad,
     pub sort: ChannelSort,
+    pub ad_type_sort: AdTypeSort,
+    pub include_inactive_ad_types: bool,
     // Market channels & balance: for the summaries page
     pub market_channels: Loadable<Vec<MarketChannel>>,
+    // The last fetch of the channels failed; cleared once one succeeds
+    pub market_unreachable: bool,
     pub balance: Loadable<EtherscanBalResp>,
     pub volume: Loadable<AnalyticsResp>,
     pub impressions: Loadable<AnalyticsResp>,
     pub daily_impressions: Loadable<AnalyticsResp>,
     pub yearly_impressions: Loadable<AnalyticsResp>,
+    // USD prices of the deposit assets; static ones are used until they're loaded
+    pub prices: Loadable<PricesResp>,
     // Current selected channel: for ChannelDetail
     pub channel: Loadable<Channel>,
     pub last_loaded: i64,
+    // The time of the last update, in seconds: the view only looks at this, never at the clock
+    pub now: i64,
+    // The first paid amount we've seen for each channel: used for pacing projections
+    pub paid_observations: pacing::PaidObservations,
+    // The channels as of the refresh before the current one
+    pub previous_channels: Option<Vec<MarketChannel>>,
+    pub status_history: status_history::StatusHistory,
+    pub language: Language,
+    pub zone: Zone,
+    pub currency: Currency,
+    pub watchlist: watchlist::Watchlist,
+    // Snapshots of the summary cards, persisted locally
+    pub history: Vec<history::MetricsSnapshot>,
+    pub previous_visit: Option<history::MetricsSnapshot>,
+    // When each source was last fetched, and how many times in a row it failed
+    pub schedule: refresh::Schedule,
+    pub in_flight: InFlight,
+    // In seconds
+    pub staleness_threshold: i64,
+    pub endpoints: Endpoints,
+    pub alert_config: alerts::AlertConfig,
+    pub alert_form: alerts_page::AlertForm,
+    // The latest matches of the alert rules, most recent first
+    pub alerts: Vec<alerts::Alert>,
+    // Serve the bundled fixtures instead of fetching
+    pub demo: Option<DemoConfig>,
 }
 
 // Update
@@ -67,6 +142,16 @@ pub enum ActionLoad {
     // Channels will show the summary plus the channels
     ChannelsActive,
     ChannelsAll,
+    // Channels entering their withdraw period within the given number of days
+    ChannelsExpiring(i64),
+    // Channels which have at least one unit of the given ad type
+    ChannelsByAdType(String),
+    // Summary plus the most targeted tags across active campaigns
+    Targeting,
+    // Summary plus the alert rules and their latest matches
+    Alerts,
+    // Summary plus the starred channels, advertisers and publishers
+    Watchlist,
     // The channel detail contains a summary of what validator knows about a channel
     ChannelDetail(String),
 }
@@ -77,266 +162,880 @@ impl Default for ActionLoad {
 }
 
 impl ActionLoad {
-    fn perform_effects(&self, orders: &mut impl Orders<Msg>) {
+    // The sources needed to render this page
+    fn sources(&self) -> &'static [Source] {
         match self {
-            ActionLoad::Summary | ActionLoad::ChannelsActive | ActionLoad::ChannelsAll => {
-                // Load on-chain balances
-                let etherscan_uri = format!(
-                    "{}?module=account&action=tokenbalance&contractAddress={}&address={}&tag=latest&apikey={}",
-                    ETHERSCAN_URL,
-                    DAI_ADDR,
-                    CORE_ADDR,
-                    ETHERSCAN_API_KEY
-                );
-                orders.perform_cmd(
-                    Request::new(etherscan_uri)
-                        .method(Method::Get)
-                        .fetch_json_data(Msg::BalanceLoaded),
-                );
+            // The detail page is rendered from what the market knows about the campaign,
+            // with its amounts valued at the current prices
+            ActionLoad::ChannelDetail(_) => &[Source::Channels, Source::Prices],
+            _ => &Source::ALL,
+        }
+    }
 
-                // Load campaigns from the market
-                orders.perform_cmd(
-                    Request::new(format!("{}/campaigns?all", MARKET_URL))
-                        .method(Method::Get)
-                        .fetch_json_data(Msg::ChannelsLoaded),
-                );
+    fn perform_effects(
+        &self,
+        requests: &[(Source, RequestId)],
+        in_flight: &InFlight,
+        endpoints: &Endpoints,
+        demo: Option<DemoConfig>,
+        orders: &mut impl Orders<Msg>,
+    ) {
+        for (source, request_id) in requests {
+            let url = match demo {
+                Some(demo) => demo.data_url(*source),
+                None => endpoints.source_url(*source),
+            };
+            fetch_source(*source, *request_id, url, in_flight, demo, orders);
+        }
 
-                // Load volume
-                orders.perform_cmd(
-                    Request::new(String::from(DAILY_VOL_URL))
-                        .method(Method::Get)
-                        .fetch_json_data(Msg::VolumeLoaded),
-                );
-                orders.perform_cmd(
-                    Request::new(String::from(IMPRESSIONS_URL))
-                        .method(Method::Get)
-                        .fetch_json_data(Msg::ImpressionsLoaded),
-                );
-                orders.perform_cmd(
-                    Request::new(String::from(DAILY_IMPRESSIONS_URL))
-                        .method(Method::Get)
-                        .fetch_json_data(Msg::DailyImpressionsLoaded),
-                );
-                orders.perform_cmd(
-                    Request::new(String::from(YEARLY_IMPRESSIONS_URL))
-                        .method(Method::Get)
-                        .fetch_json_data(Msg::YearlyImpressionsLoaded),
-                );
-            }
-            // NOTE: not used yet
-            ActionLoad::ChannelDetail(id) => {
-                let market_uri = format!(
-                    "{}/channel/{}/events-aggregates/{}?timeframe=hour&limit=168",
-                    MARKET_URL,
-                    &id,
-                    // @TODO get rid of this default earner thing, it's very very temporary
-                    // we should get an aggr of all earners
-                    DEFAULT_EARNER
-                );
-                // @TODO
-            }
+        // NOTE: not used yet
+        if let ActionLoad::ChannelDetail(id) = self {
+            let market_uri = format!(
+                "{}/channel/{}/events-aggregates/{}?timeframe=hour&limit=168",
+                endpoints.market,
+                &id,
+                // @TODO get rid of this default earner thing, it's very very temporary
+                // we should get an aggr of all earners
+                DEFAULT_EARNER
+            );
+            // @TODO
         }
     }
 }
 
+fn request(url: String, source: Source, in_flight: &InFlight) -> Request {
+    refresh::supersede(&mut in_flight.borrow_mut(), source);
+    let in_flight = in_flight.clone();
+    Request::new(url)
+        .method(Method::Get)
+        .controller(move |controller| {
+            in_flight.borrow_mut().insert(source, controller);
+        })
+}
+
+fn fetch_source(
+    source: Source,
+    request_id: RequestId,
+    url: String,
+    in_flight: &InFlight,
+    demo: Option<DemoConfig>,
+    orders: &mut impl Orders<Msg>,
+) {
+    let request = request(url, source, in_flight);
+    match source {
+        Source::Balance => perform_fetch(demo, orders, move || {
+            request.fetch_json_data(move |resp| Msg::BalanceLoaded(request_id, resp))
+        }),
+        Source::Channels => perform_fetch(demo, orders, move || {
+            request.fetch_json_data(move |resp| Msg::ChannelsLoaded(request_id, resp))
+        }),
+        Source::Volume => perform_fetch(demo, orders, move || {
+            request.fetch_json_data(move |resp| Msg::VolumeLoaded(request_id, resp))
+        }),
+        Source::Impressions => perform_fetch(demo, orders, move || {
+            request.fetch_json_data(move |resp| Msg::ImpressionsLoaded(request_id, resp))
+        }),
+        Source::DailyImpressions => perform_fetch(demo, orders, move || {
+            request.fetch_json_data(move |resp| Msg::DailyImpressionsLoaded(request_id, resp))
+        }),
+        Source::YearlyImpressions => perform_fetch(demo, orders, move || {
+            request.fetch_json_data(move |resp| Msg::YearlyImpressionsLoaded(request_id, resp))
+        }),
+        Source::Prices => perform_fetch(demo, orders, move || {
+            request.fetch_json_data(move |resp| Msg::PricesLoaded(request_id, resp))
+        }),
+    }
+}
+
+// In demo mode, the fetch only starts after the artificial latency
+fn perform_fetch<F, Fut>(demo: Option<DemoConfig>, orders: &mut impl Orders<Msg>, fetch: F)
+where
+    F: FnOnce() -> Fut + 'static,
+    Fut: Future<Item = Msg, Error = Msg> + 'static,
+{
+    match demo {
+        Some(demo) => orders.perform_cmd(demo.delay().then(move |_| fetch())),
+        None => orders.perform_cmd(fetch()),
+    };
+}
+
 #[derive(Clone)]
 pub enum Msg {
     Load(ActionLoad),
+    // Periodic check for sources that are due for a refresh
+    Tick,
+    // Refresh all sources now
     Refresh,
-    BalanceLoaded(fetch::ResponseDataResult<EtherscanBalResp>),
-    ChannelsLoaded(fetch::ResponseDataResult<Vec<MarketChannel>>),
-    VolumeLoaded(fetch::ResponseDataResult<AnalyticsResp>),
-    ImpressionsLoaded(fetch::ResponseDataResult<AnalyticsResp>),
-    DailyImpressionsLoaded(fetch::ResponseDataResult<AnalyticsResp>),
-    YearlyImpressionsLoaded(fetch::ResponseDataResult<AnalyticsResp>),
+    RefreshRateSelected(String),
+    StalenessThresholdSelected(String),
+    LanguageSelected(String),
+    ZoneSelected(String),
+    CurrencySelected(String),
+    BalanceLoaded(RequestId, fetch::ResponseDataResult<EtherscanBalResp>),
+    ChannelsLoaded(RequestId, fetch::ResponseDataResult<Vec<MarketChannel>>),
+    VolumeLoaded(RequestId, fetch::ResponseDataResult<AnalyticsResp>),
+    ImpressionsLoaded(RequestId, fetch::ResponseDataResult<AnalyticsResp>),
+    DailyImpressionsLoaded(RequestId, fetch::ResponseDataResult<AnalyticsResp>),
+    YearlyImpressionsLoaded(RequestId, fetch::ResponseDataResult<AnalyticsResp>),
+    PricesLoaded(RequestId, fetch::ResponseDataResult<PricesResp>),
     SortSelected(String),
+    AdTypeSortSelected(AdTypeSort),
+    ToggleInactiveAdTypes,
+    AlertFormInput(alerts_page::AlertField, String),
+    AddAlertRule,
+    RemoveAlertRule(usize),
+    ToggleAlertNotifications,
+    AlertWebhookDelivered(fetch::ResponseDataResult<String>),
+    ToggleWatch(watchlist::WatchKind, String),
+}
+
+impl Msg {
+    // The fetch this message is a response to
+    fn response_to(&self) -> Option<(Source, RequestId)> {
+        match self {
+            Msg::BalanceLoaded(id, _) => Some((Source::Balance, *id)),
+            Msg::ChannelsLoaded(id, _) => Some((Source::Channels, *id)),
+            Msg::VolumeLoaded(id, _) => Some((Source::Volume, *id)),
+            Msg::ImpressionsLoaded(id, _) => Some((Source::Impressions, *id)),
+            Msg::DailyImpressionsLoaded(id, _) => Some((Source::DailyImpressions, *id)),
+            Msg::YearlyImpressionsLoaded(id, _) => Some((Source::YearlyImpressions, *id)),
+            Msg::PricesLoaded(id, _) => Some((Source::Prices, *id)),
+            _ => None,
+        }
+    }
 }
 
 fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
+    // Drop responses to fetches which were superseded or belong to a page we navigated away from
+    if let Some((source, request_id)) = msg.response_to() {
+        if !model.schedule.is_current(source, request_id) {
+            orders.skip();
+            return;
+        }
+        model.in_flight.borrow_mut().remove(&source);
+    }
+    model.now = now_seconds();
+    // Demo data, and data from overridden endpoints, must not end up in the cache or the history
+    // of the real explorer
+    let persist = model.demo.is_none() && model.endpoints == Endpoints::default();
+
     match msg {
         Msg::Load(load_action) => {
             // Do not render
             orders.skip();
             // Perform the effects
-            load_action.perform_effects(orders);
+            model.schedule.invalidate();
+            refresh::abort_all(&mut model.in_flight.borrow_mut());
+            let requests = model
+                .schedule
+                .start(load_action.sources(), js_sys::Date::now());
+            load_action.perform_effects(
+                &requests,
+                &model.in_flight,
+                &model.endpoints,
+                model.demo,
+                orders,
+            );
             // This can be used on refresh
             model.load_action = load_action;
         }
+        Msg::Tick => {
+            // Do not refresh or re-render in the background; otherwise, re-rendering keeps the
+            // relative times ticking
+            if seed::document().hidden() {
+                orders.skip();
+                return;
+            }
+            let due = model
+                .schedule
+                .due(model.load_action.sources(), js_sys::Date::now());
+            if !due.is_empty() {
+                let requests = model.schedule.start(&due, js_sys::Date::now());
+                model.load_action.perform_effects(
+                    &requests,
+                    &model.in_flight,
+                    &model.endpoints,
+                    model.demo,
+                    orders,
+                );
+            }
+        }
         Msg::Refresh => {
             orders.skip();
-            model.load_action.perform_effects(orders);
-        }
-        Msg::BalanceLoaded(Ok(resp)) => model.balance = Ready(resp),
-        Msg::BalanceLoaded(Err(reason)) => log!("BalanceLoaded error:", reason),
-        Msg::ChannelsLoaded(Ok(channels)) => {
-            model.market_channels = Ready(channels);
-            model.last_loaded = (js_sys::Date::now() as i64) / 1000;
-        }
-        Msg::ChannelsLoaded(Err(reason)) => log!("ChannelsLoaded error:", reason),
-        Msg::VolumeLoaded(Ok(vol)) => model.volume = Ready(vol),
-        Msg::VolumeLoaded(Err(reason)) => log!("VolumeLoaded error:", reason),
-        Msg::ImpressionsLoaded(Ok(impressions)) => model.impressions = Ready(impressions),
-        Msg::ImpressionsLoaded(Err(reason)) => log!("ImpressionsLoaded error:", reason),
-        Msg::DailyImpressionsLoaded(Ok(impressions)) => model.daily_impressions = Ready(impressions),
-        Msg::DailyImpressionsLoaded(Err(reason)) => log!("DailyImpressionsLoaded error:", reason),
-        Msg::YearlyImpressionsLoaded(Ok(impressions)) => model.yearly_impressions = Ready(impressions),
-        Msg::YearlyImpressionsLoaded(Err(reason)) => log!("YearlyImpressionsLoaded error:", reason),
+            model.schedule.reset_backoff();
+            let requests = model
+                .schedule
+                .start(model.load_action.sources(), js_sys::Date::now());
+            model.load_action.perform_effects(
+                &requests,
+                &model.in_flight,
+                &model.endpoints,
+                model.demo,
+                orders,
+            );
+        }
+        Msg::RefreshRateSelected(rate_name) => {
+            model.schedule.rate = rate_name.into();
+            cache::write(REFRESH_RATE_KEY, &model.schedule.rate);
+        }
+        Msg::StalenessThresholdSelected(threshold) => {
+            model.staleness_threshold = threshold.parse().unwrap_or(DEFAULT_STALENESS_THRESHOLD);
+            cache::write(STALENESS_THRESHOLD_KEY, &model.staleness_threshold);
+        }
+        Msg::LanguageSelected(code) => {
+            model.language = Language::from_code(&code).unwrap_or_default();
+            cache::write(LANGUAGE_KEY, &model.language);
+        }
+        Msg::ZoneSelected(code) => {
+            model.zone = Zone::from_code(&code).unwrap_or_default();
+            cache::write(ZONE_KEY, &model.zone);
+        }
+        Msg::CurrencySelected(code) => {
+            model.currency = Currency::from_code(&code).unwrap_or_default();
+            cache::write(CURRENCY_KEY, &model.currency);
+        }
+        Msg::BalanceLoaded(_, Ok(resp)) => {
+            model.schedule.succeeded(Source::Balance);
+            let previous = alerts::MarketState::before_refresh(&model.market_channels, &model.balance);
+            let alerts = alerts::evaluate(
+                model.now,
+                model.language,
+                &model.alert_config.rules,
+                previous,
+                alerts::MarketState {
+                    locked_on_chain: Some(&resp.result),
+                    ..previous
+                },
+            );
+            alerts_page::fire(&model.alert_config, &mut model.alerts, alerts, orders);
+            if persist {
+                cache::store(BALANCE_CACHE, model.now, &resp);
+            }
+            model.balance = Ready(resp, model.now);
+        }
+        Msg::BalanceLoaded(_, Err(reason)) => {
+            log!("BalanceLoaded error:", reason);
+            model.schedule.failed(Source::Balance);
+        }
+        Msg::ChannelsLoaded(_, Ok(channels)) => {
+            model.schedule.succeeded(Source::Channels);
+            model.market_unreachable = false;
+            let previous = alerts::MarketState::before_refresh(&model.market_channels, &model.balance);
+            let alerts = alerts::evaluate(
+                model.now,
+                model.language,
+                &model.alert_config.rules,
+                previous,
+                alerts::MarketState {
+                    channels: &channels,
+                    ..previous
+                },
+            );
+            alerts_page::fire(&model.alert_config, &mut model.alerts, alerts, orders);
+            model.last_loaded = model.now;
+            if persist {
+                cache::store(CHANNELS_CACHE, model.last_loaded, &channels);
+            }
+            pacing::observe(&mut model.paid_observations, model.last_loaded, &channels);
+            if status_history::observe(&mut model.status_history, &channels) && persist {
+                status_history::save(&model.status_history);
+            }
+            let locked_on_chain = model.balance.value().map(|resp| resp.result.clone());
+            if persist {
+                history::record(
+                    &mut model.history,
+                    history::MetricsSnapshot::new(model.last_loaded, channels.iter(), locked_on_chain),
+                );
+            }
+            let previous = std::mem::replace(
+                &mut model.market_channels,
+                Ready(channels, model.last_loaded),
+            );
+            // What was restored from the cache may be days old; the changes are since the last refresh
+            model.previous_channels = match previous {
+                Ready(previous, _) => Some(previous),
+                _ => None,
+            };
+        }
+        Msg::ChannelsLoaded(_, Err(reason)) => {
+            log!("ChannelsLoaded error:", reason);
+            model.schedule.failed(Source::Channels);
+            model.market_unreachable = true;
+        }
+        Msg::VolumeLoaded(_, Ok(vol)) => {
+            model.schedule.succeeded(Source::Volume);
+            if persist {
+                cache::store(VOLUME_CACHE, model.now, &vol);
+            }
+            model.volume = Ready(vol, model.now);
+        }
+        Msg::VolumeLoaded(_, Err(reason)) => {
+            log!("VolumeLoaded error:", reason);
+            model.schedule.failed(Source::Volume);
+        }
+        Msg::ImpressionsLoaded(_, Ok(impressions)) => {
+            model.schedule.succeeded(Source::Impressions);
+            if persist {
+                cache::store(IMPRESSIONS_CACHE, model.now, &impressions);
+            }
+            model.impressions = Ready(impressions, model.now);
+        }
+        Msg::ImpressionsLoaded(_, Err(reason)) => {
+            log!("ImpressionsLoaded error:", reason);
+            model.schedule.failed(Source::Impressions);
+        }
+        Msg::DailyImpressionsLoaded(_, Ok(impressions)) => {
+            model.schedule.succeeded(Source::DailyImpressions);
+            if persist {
+                cache::store(DAILY_IMPRESSIONS_CACHE, model.now, &impressions);
+            }
+            model.daily_impressions = Ready(impressions, model.now);
+        }
+        Msg::DailyImpressionsLoaded(_, Err(reason)) => {
+            log!("DailyImpressionsLoaded error:", reason);
+            model.schedule.failed(Source::DailyImpressions);
+        }
+        Msg::YearlyImpressionsLoaded(_, Ok(impressions)) => {
+            model.schedule.succeeded(Source::YearlyImpressions);
+            if persist {
+                cache::store(YEARLY_IMPRESSIONS_CACHE, model.now, &impressions);
+            }
+            model.yearly_impressions = Ready(impressions, model.now);
+        }
+        Msg::YearlyImpressionsLoaded(_, Err(reason)) => {
+            log!("YearlyImpressionsLoaded error:", reason);
+            model.schedule.failed(Source::YearlyImpressions);
+        }
+        Msg::PricesLoaded(_, Ok(prices)) => {
+            model.schedule.succeeded(Source::Prices);
+            if persist {
+                cache::store(PRICES_CACHE, model.now, &prices);
+            }
+            model.prices = Ready(prices, model.now);
+        }
+        Msg::PricesLoaded(_, Err(reason)) => {
+            log!("PricesLoaded error:", reason);
+            model.schedule.failed(Source::Prices);
+        }
         Msg::SortSelected(sort_name) => model.sort = sort_name.into(),
+        Msg::AdTypeSortSelected(sort) => model.ad_type_sort = sort,
+        Msg::ToggleInactiveAdTypes => {
+            model.include_inactive_ad_types = !model.include_inactive_ad_types
+        }
+        Msg::AlertFormInput(field, value) => {
+            let form = &mut model.alert_form;
+            match field {
+                alerts_page::AlertField::Kind => form.kind = value,
+                alerts_page::AlertField::Creator => form.creator = value,
+                alerts_page::AlertField::Status => form.status = value,
+                alerts_page::AlertField::Percent => form.percent = value,
+                alerts_page::AlertField::Amount => form.amount = value,
+                alerts_page::AlertField::Webhook => {
+                    let webhook = value.trim();
+                    model.alert_config.webhook = Some(webhook)
+                        .filter(|x| !x.is_empty())
+                        .map(str::to_string);
+                    alerts_page::save(&model.alert_config);
+                }
+            }
+        }
+        Msg::AddAlertRule => match model.alert_form.rule() {
+            Some(rule) => {
+                model.alert_config.rules.push(rule);
+                alerts_page::save(&model.alert_config);
+                model.alert_form = Default::default();
+            }
+            None => log!("Invalid alert rule"),
+        },
+        Msg::RemoveAlertRule(i) => {
+            if i < model.alert_config.rules.len() {
+                model.alert_config.rules.remove(i);
+                alerts_page::save(&model.alert_config);
+            }
+        }
+        Msg::ToggleAlertNotifications => {
+            model.alert_config.notify = !model.alert_config.notify;
+            if model.alert_config.notify {
+                alerts_page::request_notification_permission();
+            }
+            alerts_page::save(&model.alert_config);
+        }
+        Msg::ToggleWatch(kind, id) => {
+            model.watchlist.toggle(kind, &id);
+            watchlist::save(&model.watchlist);
+        }
+        Msg::AlertWebhookDelivered(Ok(_)) => {
+            orders.skip();
+        }
+        Msg::AlertWebhookDelivered(Err(reason)) => {
+            orders.skip();
+            log!("AlertWebhookDelivered error:", reason);
+        }
     }
 }
 
 // View
 fn view(model: &Model) -> Node<Msg> {
-    let channels = match &model.market_channels {
-        Loading => return h2!["Loading..."],
-        Ready(c) => c,
+    let lang = model.language;
+    let freshness = Freshness::new(model);
+    let channels = match model.market_channels.value() {
+        // With nothing cached either, there's nothing to show until a refresh succeeds
+        None if model.market_unreachable => {
+            return div![
+                refresh_controls(
+                    lang,
+                    model.zone,
+                    model.currency,
+                    model.schedule.rate,
+                    model.staleness_threshold
+                ),
+                h3![class!["stale"], lang.t("The market is unreachable, and nothing was cached yet")],
+            ]
+        }
+        None => return h2![lang.t("Loading...")],
+        Some(c) => c,
     };
-
     let channels_dai = channels
         .iter();
         // disabled cause of the SAI to DAI migration
         // .filter(|MarketChannel { deposit_asset, .. }| deposit_asset == DAI_ADDR);
 
-    let total_paid = channels_dai.clone().map(|x| x.status.balances_sum()).sum();
-    let total_deposit = channels_dai
-        .clone()
-        .map(|MarketChannel { deposit_amount, .. }| deposit_amount)
-        .sum();
-
-    let unique_units = &channels
-        .iter()
-        .flat_map(|x| &x.spec.ad_units)
-        .map(|x| &x.ipfs)
-        .collect::<HashSet<_>>();
-
-    let unique_publishers = channels_dai
-        .clone()
-        .flat_map(|x| {
-            x.status
-                .balances
-                .keys()
-                .map(|k| k.to_lowercase())
-                .filter(|k| *k != x.creator.to_lowercase())
-                .collect::<Vec<_>>()
-        })
-        .collect::<HashSet<_>>();
+    let locked_on_chain = model.balance.value().map(|resp| resp.result.clone());
+    let current = history::MetricsSnapshot::new(model.last_loaded, channels_dai.clone(), locked_on_chain);
+    let changes = model
+        .previous_channels
+        .as_ref()
+        .map(|previous| changes::channel_changes(previous, channels))
+        .unwrap_or_default();
 
-    let unique_advertisers = channels_dai
-        .clone()
-        .map(|x| x.creator.to_lowercase())
-        .collect::<HashSet<_>>();
- 
     div![
-        // Cards
-        card("Campaigns", Ready(channels.len().to_string())),
-        card("Ad units", Ready(unique_units.len().to_string())),
-        card("Publishers", Ready(unique_publishers.len().to_string())),
-        card("Advertisers", Ready(unique_advertisers.len().to_string())),
-        impressions_card("Monthly impressions", &model.impressions),
-        impressions_card("Daily impressions", &model.daily_impressions),
-        br![],
-        card(
-            "Total campaign deposits",
-            Ready(dai_readable(&total_deposit))
+        refresh_controls(
+            lang,
+            model.zone,
+            model.currency,
+            model.schedule.rate,
+            model.staleness_threshold
         ),
-        card("Paid out", Ready(dai_readable(&total_paid))),
-        a![
-            attrs! { At::Href => format!("https://etherscan.io/address/{}#tokentxns", CORE_ADDR) },
-            card(
-                "Locked up on-chain",
-                match &model.balance {
-                    Ready(resp) => Ready(dai_readable(&resp.result)),
-                    Loading => Loading,
-                }
-            ),
-        ],
-        volume_card(
-            "24h volume",
-            match &model.volume {
-                Ready(vol) => Ready(dai_readable(&vol.aggr.iter().map(|x| &x.value).sum())),
-                Loading => Loading,
-            },
-            &model.volume
-        ),
-        br![],
-        impressions_card("Yearly impressions (transactions)", &model.yearly_impressions),
+        // Not while the cached channels are being refreshed, only once that has failed
+        match (model.market_unreachable, model.market_channels.fetched_at()) {
+            (true, Some(time)) => h3![
+                class!["stale"],
+                lang.tf(
+                    "The market is unreachable, showing data as of {}",
+                    &[&lang.date_time(model.zone, &Utc.timestamp(time, 0))]
+                )
+            ],
+            _ => seed::empty(),
+        },
+        // The detail page only loads the channels, which the cards aren't all derived from
+        match &model.load_action {
+            ActionLoad::ChannelDetail(_) => vec![],
+            _ => summary_cards(freshness, model, &current),
+        },
+        match &model.load_action {
+            ActionLoad::ChannelDetail(_) => seed::empty(),
+            _ => history::history_charts(lang, model.zone, &model.history),
+        },
         // Tables
-        if model.load_action == ActionLoad::ChannelsActive || model.load_action == ActionLoad::ChannelsAll {
-            div![
+        match &model.load_action {
+            ActionLoad::ChannelsActive | ActionLoad::ChannelsAll | ActionLoad::ChannelsByAdType(_) => div![
+                match &model.load_action {
+                    ActionLoad::ChannelsByAdType(ad_type) => h2![lang.tf("Campaigns with {} units", &[ad_type])],
+                    _ => seed::empty(),
+                },
                 select![
                     attrs! {At::Value => "deposit"},
-                    option![attrs! {At::Value => "deposit"}, "Sort by deposit"],
-                    option![attrs! {At::Value => "status"}, "Sort by status"],
-                    option![attrs! {At::Value => "created"}, "Sort by created"],
+                    option![attrs! {At::Value => "deposit"}, lang.t("Sort by deposit")],
+                    option![attrs! {At::Value => "status"}, lang.t("Sort by status")],
+                    option![attrs! {At::Value => "created"}, lang.t("Sort by created")],
                     input_ev(Ev::Input, Msg::SortSelected)
                 ],
+                changes_panel(lang, model.previous_channels.is_some(), &changes, channels),
                 channel_table(
+                    freshness,
                     model.last_loaded,
+                    &model.paid_observations,
+                    Some(&model.watchlist),
+                    &changes,
                     &channels_dai
                         .clone()
-                        .filter(|channel| model.load_action == ActionLoad::ChannelsAll || match channel.status.status_type {
-                            types::MarketStatusType::Expired | types::MarketStatusType::Exhausted => false,
-                            _ => true
-                        })
-                        .sorted_by(|x, y| match model.sort {
-                            ChannelSort::Deposit => y.deposit_amount.cmp(&x.deposit_amount),
-                            ChannelSort::Status => x.status.status_type.cmp(&y.status.status_type),
-                            ChannelSort::Created => y.spec.created.cmp(&x.spec.created),
+                        .filter(|channel| match &model.load_action {
+                            ActionLoad::ChannelsAll => true,
+                            ActionLoad::ChannelsByAdType(ad_type) => channel
+                                .spec
+                                .ad_units
+                                .iter()
+                                .any(|unit| &unit.ad_type == ad_type),
+                            _ => !channel.status.status_type.is_finished(),
                         })
+                        .sorted_by(|x, y| model.sort.compare(x, y))
                         .collect::<Vec<_>>()
                 ),
-            ]
-        } else {
-            seed::empty()
+            ],
+            ActionLoad::ChannelsExpiring(days) => div![
+                h2![lang.tn(
+                    "Entering withdraw period in the next {} day",
+                    "Entering withdraw period in the next {} days",
+                    *days
+                )],
+                changes_panel(lang, model.previous_channels.is_some(), &changes, channels),
+                channel_table(
+                    freshness,
+                    model.last_loaded,
+                    &model.paid_observations,
+                    Some(&model.watchlist),
+                    &changes,
+                    &channels_dai
+                        .clone()
+                        .filter(|channel| lifecycle::is_expiring_within(model.last_loaded, *days, channel))
+                        .sorted_by(|x, y| x.spec.withdraw_period_start.cmp(&y.spec.withdraw_period_start))
+                        .collect::<Vec<_>>()
+                ),
+            ],
+            ActionLoad::Targeting => div![
+                h2![lang.t("Most targeted tags")],
+                targeting::targeting_table(lang, freshness.valuation, &channels_dai.clone().collect::<Vec<_>>()),
+            ],
+            ActionLoad::Alerts => alerts_page::alerts_page(
+                lang,
+                &model.alert_config,
+                &model.alert_form,
+                &model.alerts,
+                model.zone
+            ),
+            ActionLoad::Watchlist => watchlist::watchlist_page(
+                lang,
+                freshness.valuation,
+                &model.watchlist,
+                channels,
+                model.previous_channels.as_ref().map(Vec::as_slice)
+            ),
+            ActionLoad::ChannelDetail(id) => match channels.iter().find(|c| &c.id == id) {
+                Some(channel) => channel_detail(
+                    freshness,
+                    &model.watchlist,
+                    model.status_history.get(&channel.id).map(Vec::as_slice).unwrap_or(&[]),
+                    channel
+                ),
+                None => h2![lang.tf("Channel {} not found", &[id])],
+            },
+            _ => seed::empty(),
         },
-        ad_unit_stats_table(&channels_dai.clone().collect::<Vec<_>>()),
+        ad_unit_stats_table(
+            lang,
+            freshness.valuation,
+            &stats::ad_type_stats(channels_dai.clone()),
+            model.ad_type_sort,
+            model.include_inactive_ad_types,
+            true
+        ),
         a![
             attrs! { At::Href => "https://platform.adex.network/#/"},
             div![
                 class!["button"],
-                "Go to platform"
+                lang.t("Go to platform")
             ]
         ],
         a![
             attrs! { At::Href => "https://www.adex.network/"},
             div![
                 class!["button"],
-                "Go to website"
+                lang.t("Go to website")
             ]
         ]
     ]
 }
 
-fn impressions_card(title: &str, loadable: &types::Loadable<AnalyticsResp>) -> Node<Msg> {
+// The cards of the summary, with the changes since the previous visit
+fn summary_cards(
+    freshness: Freshness,
+    model: &Model,
+    current: &history::MetricsSnapshot,
+) -> Vec<Node<Msg>> {
+    // Cards derived from the channels are as fresh as the channels themselves
+    let from_channels = |value: Compact| model.market_channels.as_ref().map(|_| value);
+    let previous = model.previous_visit.as_ref();
+    let lang = freshness.lang;
+    let valuation = freshness.valuation;
+    let locked_on_chain = card_with_note(
+        lang.t("Locked up on-chain"),
+        model.balance.as_ref().map(|resp| valuation.compact(lang, DAI_ADDR, &resp.result)),
+        match (&current.locked_on_chain, previous.and_then(|p| p.locked_on_chain.as_ref())) {
+            (Some(locked), Some(prev_locked)) => history::dai_delta(lang, locked, prev_locked),
+            _ => None,
+        },
+        freshness
+    );
+    // Valued channel by channel, at the price of each deposit asset
+    let channels = model.market_channels.value().map(Vec::as_slice).unwrap_or(&[]);
+    let total_deposit_usd = valuation.usd_total(
+        channels
+            .iter()
+            .map(|c| (c.deposit_asset.as_str(), c.deposit_amount.clone())),
+    );
+    let paid_out_usd = valuation.usd_total(
+        channels
+            .iter()
+            .map(|c| (c.deposit_asset.as_str(), c.status.balances_sum())),
+    );
+
+    vec![
+        card_with_note(
+            lang.t("Campaigns"),
+            from_channels(lang.compact_count(&BigNum::from(current.summary.campaigns as u64))),
+            previous.and_then(|p| history::count_delta(lang, current.summary.campaigns, p.summary.campaigns)),
+            freshness
+        ),
+        card_with_note(
+            lang.t("Ad units"),
+            from_channels(lang.compact_count(&BigNum::from(current.summary.ad_units as u64))),
+            previous.and_then(|p| history::count_delta(lang, current.summary.ad_units, p.summary.ad_units)),
+            freshness
+        ),
+        card_with_note(
+            lang.t("Publishers"),
+            from_channels(lang.compact_count(&BigNum::from(current.summary.publishers as u64))),
+            previous.and_then(|p| history::count_delta(lang, current.summary.publishers, p.summary.publishers)),
+            freshness
+        ),
+        card_with_note(
+            lang.t("Advertisers"),
+            from_channels(lang.compact_count(&BigNum::from(current.summary.advertisers as u64))),
+            previous.and_then(|p| history::count_delta(lang, current.summary.advertisers, p.summary.advertisers)),
+            freshness
+        ),
+        impressions_card(lang.t("Monthly impressions"), &model.impressions, freshness),
+        impressions_card(lang.t("Daily impressions"), &model.daily_impressions, freshness),
+        br![],
+        card_with_note(
+            lang.t("Total campaign deposits"),
+            from_channels(valuation.compact_with_usd(lang, &current.summary.total_deposit, total_deposit_usd)),
+            previous.and_then(|p| history::dai_delta(lang, &current.summary.total_deposit, &p.summary.total_deposit)),
+            freshness
+        ),
+        card_with_note(
+            lang.t("Paid out"),
+            from_channels(valuation.compact_with_usd(lang, &current.summary.paid_out, paid_out_usd)),
+            previous.and_then(|p| history::dai_delta(lang, &current.summary.paid_out, &p.summary.paid_out)),
+            freshness
+        ),
+        if freshness.interactive {
+            a![
+                attrs! { At::Href => format!("https://etherscan.io/address/{}#tokentxns", CORE_ADDR) },
+                locked_on_chain
+            ]
+        } else {
+            locked_on_chain
+        },
+        volume_card(
+            lang.t("24h volume"),
+            model
+                .volume
+                .as_ref()
+                .map(|vol| valuation.compact(lang, DAI_ADDR, &vol.aggr.iter().map(|x| &x.value).sum())),
+            &model.volume,
+            freshness
+        ),
+        br![],
+        impressions_card(lang.t("Yearly impressions (transactions)"), &model.yearly_impressions, freshness),
+    ]
+}
+
+fn refresh_controls(
+    lang: Language,
+    zone: Zone,
+    currency: Currency,
+    rate: RefreshRate,
+    staleness_threshold: i64,
+) -> Node<Msg> {
+    let rate_option = |value: &str, label: &str, option_rate: RefreshRate| {
+        if rate == option_rate {
+            option![attrs! {At::Value => value; At::Selected => true}, label]
+        } else {
+            option![attrs! {At::Value => value}, label]
+        }
+    };
+    let language_option = |option_lang: Language| {
+        if lang == option_lang {
+            option![attrs! {At::Value => option_lang.code(); At::Selected => true}, option_lang.name()]
+        } else {
+            option![attrs! {At::Value => option_lang.code()}, option_lang.name()]
+        }
+    };
+    let zone_option = |option_zone: Zone| {
+        if zone == option_zone {
+            option![attrs! {At::Value => option_zone.code(); At::Selected => true}, lang.t(option_zone.label())]
+        } else {
+            option![attrs! {At::Value => option_zone.code()}, lang.t(option_zone.label())]
+        }
+    };
+    let currency_option = |option_currency: Currency| {
+        if currency == option_currency {
+            option![attrs! {At::Value => option_currency.code(); At::Selected => true}, lang.t(option_currency.label())]
+        } else {
+            option![attrs! {At::Value => option_currency.code()}, lang.t(option_currency.label())]
+        }
+    };
+    let threshold_option = |seconds: i64, label: &str| {
+        if seconds == staleness_threshold {
+            option![attrs! {At::Value => seconds.to_string(); At::Selected => true}, label]
+        } else {
+            option![attrs! {At::Value => seconds.to_string()}, label]
+        }
+    };
+    div![
+        class!["refresh-controls"],
+        button![simple_ev(Ev::Click, Msg::Refresh), lang.t("Refresh now")],
+        select![
+            rate_option("fast", lang.t("Auto-refresh: fast"), RefreshRate::Fast),
+            rate_option("normal", lang.t("Auto-refresh: normal"), RefreshRate::Normal),
+            rate_option("slow", lang.t("Auto-refresh: slow"), RefreshRate::Slow),
+            rate_option("off", lang.t("Auto-refresh: off"), RefreshRate::Off),
+            input_ev(Ev::Input, Msg::RefreshRateSelected)
+        ],
+        select![
+            threshold_option(300, lang.t("Outdated after 5 minutes")),
+            threshold_option(600, lang.t("Outdated after 10 minutes")),
+            threshold_option(1800, lang.t("Outdated after 30 minutes")),
+            threshold_option(3600, lang.t("Outdated after 1 hour")),
+            input_ev(Ev::Input, Msg::StalenessThresholdSelected)
+        ],
+        select![
+            Language::ALL.iter().map(|x| language_option(*x)).collect::<Vec<_>>(),
+            input_ev(Ev::Input, Msg::LanguageSelected)
+        ],
+        select![
+            Zone::ALL.iter().map(|x| zone_option(*x)).collect::<Vec<_>>(),
+            input_ev(Ev::Input, Msg::ZoneSelected)
+        ],
+        select![
+            Currency::ALL.iter().map(|x| currency_option(*x)).collect::<Vec<_>>(),
+            input_ev(Ev::Input, Msg::CurrencySelected)
+        ],
+    ]
+}
+
+fn impressions_card(
+    title: &str,
+    loadable: &types::Loadable<AnalyticsResp>,
+    freshness: Freshness,
+) -> Node<Msg> {
     volume_card(
         title,
-        match loadable {
-            Ready(vol) => Ready(
-                vol.aggr
-                    .iter()
-                    .map(|x| &x.value)
-                    .sum::<BigNum>()
-                    .to_u64()
-                    .unwrap_or(0)
-                    .to_formatted_string(&Locale::en)
-            ),
-            Loading => Loading,
-        },
         loadable
+            .as_ref()
+            .map(|vol| freshness.lang.compact_count(&vol.aggr.iter().map(|x| &x.value).sum())),
+        loadable,
+        freshness
     )
 }
 
-fn card(label: &str, value: Loadable<String>) -> Node<Msg> {
+// The current time and how old data can get before we flag it, both in seconds,
+// and how to show times and amounts
+#[derive(Clone, Copy)]
+struct Freshness<'a> {
+    now: i64,
+    threshold: i64,
+    lang: Language,
+    zone: Zone,
+    valuation: Valuation<'a>,
+    // Without links and controls, e.g. in the static report
+    interactive: bool,
+}
+
+impl<'a> Freshness<'a> {
+    fn new(model: &'a Model) -> Self {
+        Freshness {
+            now: model.now,
+            threshold: model.staleness_threshold,
+            lang: model.language,
+            zone: model.zone,
+            valuation: Valuation {
+                currency: model.currency,
+                prices: model.prices.value(),
+            },
+            interactive: true,
+        }
+    }
+}
+
+// Rounded to the cent, in the chosen currency, with the full amounts on hover
+fn dai_amount(lang: Language, valuation: Valuation, asset: &str, amount: &BigNum) -> Node<Msg> {
+    valued_amount(lang, valuation, amount, valuation.usd(asset, amount))
+}
+
+// The same, for totals which were valued asset by asset
+fn valued_amount(
+    lang: Language,
+    valuation: Valuation,
+    amount: &BigNum,
+    usd: Option<BigNum>,
+) -> Node<Msg> {
+    let amount = valuation.amount_with_usd(lang, amount, usd);
+    span![attrs! { At::Title => amount.full }, amount.short]
+}
+
+// A relative time, which ticks with `now`, and the absolute one on hover
+fn relative_time(freshness: Freshness, t: &DateTime<Utc>, text: String) -> Node<Msg> {
+    span![
+        class!["relative-time"],
+        attrs! { At::Title => freshness.lang.timestamp(freshness.zone, t) },
+        text
+    ]
+}
+
+fn card(label: &str, value: Loadable<Compact>, freshness: Freshness) -> Node<Msg> {
+    card_with_note(label, value, None, freshness)
+}
+
+fn card_with_note(
+    label: &str,
+    value: Loadable<Compact>,
+    note: Option<String>,
+    freshness: Freshness,
+) -> Node<Msg> {
+    let updated = freshness_note(&value, freshness);
     div![
         class!["card"],
         match value {
             Loading => div![class!["card-value loading"]],
-            Ready(value) => div![class!["card-value"], value],
+            Ready(value, _) => div![class!["card-value"], attrs! { At::Title => value.full }, value.short],
+            Stale(value, _) => div![class!["card-value stale"], attrs! { At::Title => value.full }, value.short],
         },
         div![class!["card-label"], label],
+        match note {
+            Some(note) => div![class!["card-note"], note],
+            None => seed::empty(),
+        },
+        updated,
     ]
 }
 
+fn freshness_note<T>(loadable: &Loadable<T>, freshness: Freshness) -> Node<Msg> {
+    match loadable {
+        Loading => seed::empty(),
+        Ready(_, time) => div![
+            class![if freshness.now - time > freshness.threshold {
+                "card-note outdated"
+            } else {
+                "card-note"
+            }],
+            attrs! { At::Title => freshness.lang.timestamp(freshness.zone, &Utc.timestamp(*time, 0)) },
+            freshness.lang.tf(
+                "updated {}",
+                &[&freshness.lang.time_ago(freshness.zone, freshness.now, &Utc.timestamp(*time, 0))]
+            )
+        ],
+        Stale(_, time) => div![
+            class!["card-note stale"],
+            freshness.lang.tf(
+                "stale, as of {}",
+                &[&freshness.lang.date_time(freshness.zone, &Utc.timestamp(*time, 0))]
+            )
+        ],
+    }
+}
+
 fn volume_chart(vol: &AnalyticsResp) -> Option<Node<Msg>> {
     let values = vol.aggr.iter().map(|x| &x.value);
     let min = values.clone().min()?;
@@ -377,103 +1076,329 @@ fn volume_chart(vol: &AnalyticsResp) -> Option<Node<Msg>> {
     ])
 }
 
-fn volume_card(card_label: &str, val: Loadable<String>, vol: &Loadable<AnalyticsResp>) -> Node<Msg> {
-    let (card_value, vol) = match (&val, vol) {
-        (Ready(val), Ready(vol)) => (val, vol),
-        _ => return card(card_label, Loading),
+fn volume_card(
+    card_label: &str,
+    val: Loadable<Compact>,
+    vol: &Loadable<AnalyticsResp>,
+    freshness: Freshness,
+) -> Node<Msg> {
+    let (card_value, vol) = match (val.value(), vol.value()) {
+        (Some(val), Some(vol)) => (val, vol),
+        _ => return card(card_label, Loading, freshness),
     };
     match volume_chart(vol) {
         Some(chart) => div![
             class!["card chart"],
             chart,
-            div![class!["card-value"], card_value],
+            div![
+                class!["card-value"],
+                attrs! { At::Title => &card_value.full },
+                card_value.short.clone()
+            ],
             div![class!["card-label"], card_label],
+            freshness_note(&val, freshness),
         ],
-        None => card(card_label, val),
+        None => card(card_label, val, freshness),
+    }
+}
+
+// Nothing to compare with until the second refresh
+fn changes_panel(
+    lang: Language,
+    refreshed: bool,
+    changes: &HashMap<&str, changes::ChannelChange>,
+    channels: &[MarketChannel],
+) -> Node<Msg> {
+    if !refreshed {
+        return seed::empty();
     }
+    let summary = changes::summarize(changes.values());
+    // In the same order as the table would list them by default
+    let changed = channels
+        .iter()
+        .filter_map(|channel| changes.get(channel.id.as_str()).map(|change| (channel, change)))
+        .filter(|(_, change)| change.is_new || change.status.is_some())
+        .map(|(channel, change)| {
+            li![
+                a![
+                    attrs! {At::Href => format!("/channel/{}", channel.id)},
+                    channel.id.chars().take(6).collect::<String>()
+                ],
+                format!(": {}", change.describe(lang))
+            ]
+        })
+        .collect::<Vec<_>>();
+    div![
+        class!["changes-panel"],
+        h3![lang.t("Changes since last refresh")],
+        if changes.is_empty() {
+            p![lang.t("None")]
+        } else {
+            p![summary.describe(lang)]
+        },
+        ul![changed],
+    ]
 }
 
-fn channel_table(last_loaded: i64, channels: &[&MarketChannel]) -> Node<Msg> {
+// Without a watchlist, e.g. in the static report, channels can't be starred
+fn channel_table(
+    freshness: Freshness,
+    last_loaded: i64,
+    observations: &pacing::PaidObservations,
+    watchlist: Option<&watchlist::Watchlist>,
+    changes: &HashMap<&str, changes::ChannelChange>,
+    channels: &[&MarketChannel],
+) -> Node<Msg> {
+    let lang = freshness.lang;
     let header = tr![
-        td!["URL"],
-        td!["USD estimate"],
-        td!["Deposit"],
-        // td!["CPM"],
-        td!["Paid"],
-        td!["Paid - %"],
-        //td!["Max impressions"],
-        td!["Status"],
-        td!["Created"],
+        td![lang.t("URL")],
+        td![lang.t("USD estimate")],
+        td![lang.t("Deposit")],
+        td![lang.t("CPM")],
+        td![lang.t("Paid")],
+        td![lang.t("Paid - %")],
+        td![lang.t("Pacing")],
+        td![lang.t("Projected exhaustion")],
+        td![lang.t("Max impressions")],
+        td![lang.t("Status")],
+        td![lang.t("Created")],
+        td![lang.t("Withdraw period")],
+        td![lang.t("Expires")],
+        td![lang.t("Lifecycle")],
         //td!["Last updated"],
-        td!["Preview"]
+        td![lang.t("Preview")]
     ];
 
     let channels = std::iter::once(header)
-        .chain(channels.iter().map(|c| channel(last_loaded, c)))
+        .chain(
+            channels
+                .iter()
+                .map(|c| {
+                    let change = changes.get(c.id.as_str());
+                    channel(freshness, last_loaded, observations, watchlist, change, c)
+                }),
+        )
         .collect::<Vec<Node<Msg>>>();
 
     table![channels]
 }
 
-fn channel(last_loaded: i64, channel: &MarketChannel) -> Node<Msg> {
+fn channel(
+    freshness: Freshness,
+    last_loaded: i64,
+    observations: &pacing::PaidObservations,
+    watchlist: Option<&watchlist::Watchlist>,
+    change: Option<&changes::ChannelChange>,
+    channel: &MarketChannel,
+) -> Node<Msg> {
+    let lang = freshness.lang;
     let deposit_amount = &channel.deposit_amount;
     let paid_total = channel.status.balances_sum();
-    let url = format!(
-        "{}/channel/{}/status",
-        &channel.spec.validators.leader().url,
-        channel.id
-    );
     let id_prefix = channel.id.chars().take(6).collect::<String>();
     // This has a tiny issue: when you go back to the explorer after being in another window,
     // stuff will be not-recent until we get the latest status
     tr![
         class!(
-            if last_loaded - channel.status.last_checked.timestamp() > 600 {
-                "not-recent"
-            } else {
-                "recent"
+            match (
+                last_loaded - channel.status.last_checked.timestamp() > freshness.threshold,
+                change.map(|x| x.is_new).unwrap_or(false)
+            ) {
+                (true, false) => "not-recent",
+                (true, true) => "not-recent new-campaign",
+                (false, false) => "recent",
+                (false, true) => "recent new-campaign",
             }
         ),
-        td![a![
-            attrs! {At::Href => url; At::Target => "_blank"},
-            id_prefix
-        ]],
+        td![
+            match watchlist {
+                Some(watchlist) => watchlist::star(lang, watchlist, watchlist::WatchKind::Channel, &channel.id),
+                None => seed::empty(),
+            },
+            if freshness.interactive {
+                a![
+                    attrs! {At::Href => format!("/channel/{}", channel.id)},
+                    id_prefix
+                ]
+            } else {
+                span![id_prefix]
+            }
+        ],
         td![match channel.status.usd_estimate.as_ref() {
             Some(usd_estimate) => format!("${:.2}", &usd_estimate),
-            None => "N/A".to_string(),
+            None => lang.t("N/A").to_string(),
         }],
-        td![dai_readable(deposit_amount)],
-        //td![dai_readable(
-        //    &(&channel.spec.min_per_impression * &1000.into())
-        //)],
-        td![dai_readable(&paid_total)],
-        td![{
-            let base = 100_000_u64;
-            let paid_units = (&paid_total * &base.into()).div_floor(deposit_amount);
-            let paid_hundreds = paid_units.to_f64().unwrap_or(base as f64) / (base as f64 / 100.0);
-            format!("{:.3}%", paid_hundreds)
+        td![dai_amount(lang, freshness.valuation, &channel.deposit_asset, deposit_amount)],
+        td![if channel.spec.min_per_impression == channel.spec.max_per_impression {
+            lang.cpm(&channel.spec.min_per_impression)
+        } else {
+            format!(
+                "{} - {}",
+                lang.cpm(&channel.spec.min_per_impression),
+                lang.cpm(&channel.spec.max_per_impression)
+            )
         }],
-        //td![
-        //    (deposit_amount / &channel.spec.min_per_impression)
-        //        .to_u64()
-        //        .unwrap_or(0)
-        //        .to_formatted_string(&Locale::en)
-        //],
-        td![format!("{:?}", &channel.status.status_type)],
-        td![time_diff(last_loaded, &channel.spec.created)],
-        //td![time_diff(last_loaded, &channel.status.last_checked)],
+        td![
+            dai_amount(lang, freshness.valuation, &channel.deposit_asset, &paid_total),
+            match change.and_then(|x| x.paid_increase.as_ref()) {
+                Some(increase) => span![
+                    class!["change"],
+                    format!(" +{}", freshness.valuation.amount(lang, &channel.deposit_asset, increase).short)
+                ],
+                None => seed::empty(),
+            }
+        ],
+        td![paid_percentage(&paid_total, deposit_amount)],
+        match pacing::pacing(last_loaded, observations, channel) {
+            Some(pacing) => vec![
+                td![
+                    class![match pacing.status {
+                        pacing::PacingStatus::UnderPacing => "under-pacing",
+                        pacing::PacingStatus::OnPace => "on-pace",
+                        pacing::PacingStatus::OverPacing => "over-pacing",
+                    }],
+                    attrs! { At::Title => lang.tf(
+                        "{}% spent, {}% of time elapsed",
+                        &[&lang.decimal(pacing.spent * 100.0, 1), &lang.decimal(pacing.elapsed * 100.0, 1)]
+                    ) },
+                    lang.t(pacing.status.name())
+                ],
+                td![match pacing.projected_exhaustion {
+                    Some(t) => lang.date_time(freshness.zone, &t),
+                    None => lang.t("N/A").to_string(),
+                }],
+            ],
+            None => vec![td![lang.t("N/A")], td![lang.t("N/A")]],
+        },
+        if channel.spec.min_per_impression == BigNum::from(0) {
+            td![lang.t("N/A")]
+        } else {
+            let max_impressions =
+                lang.compact_count(&deposit_amount.div_floor(&channel.spec.min_per_impression));
+            td![attrs! { At::Title => max_impressions.full }, max_impressions.short]
+        },
+        match change.and_then(|x| x.status.as_ref()) {
+            Some((from, to)) => td![
+                class!["change"],
+                format!("{} → {}", lang.t(from.name()), lang.t(to.name()))
+            ],
+            None => td![lang.t(channel.status.status_type.name())],
+        },
+        td![relative_time(
+            freshness,
+            &channel.spec.created,
+            lang.time_ago(freshness.zone, freshness.now, &channel.spec.created)
+        )],
+        td![relative_time(
+            freshness,
+            &channel.spec.withdraw_period_start,
+            lifecycle::countdown(lang, freshness.now, &channel.spec.withdraw_period_start)
+        )],
+        td![relative_time(
+            freshness,
+            &channel.valid_until,
+            lifecycle::countdown(lang, freshness.now, &channel.valid_until)
+        )],
+        td![lifecycle::lifecycle_timeline(lang, freshness.zone, freshness.now, channel)],
+        //td![lang.time_ago(freshness.zone, freshness.now, &channel.status.last_checked)],
         td![class!["preview"], {
             match channel.spec.ad_units.get(0) {
-                Some(unit) => a![
+                Some(unit) if freshness.interactive => a![
                     attrs! { At::Href => &unit.target_url; At::Target => "_blank" },
                     unit_preview(&unit)
                 ],
+                Some(unit) => unit_preview(&unit),
                 None => seed::empty(),
             }
         }]
     ]
 }
 
+fn channel_detail(
+    freshness: Freshness,
+    watchlist: &watchlist::Watchlist,
+    statuses: &[status_history::StatusObservation],
+    channel: &MarketChannel,
+) -> Node<Msg> {
+    let lang = freshness.lang;
+    let amount = |amount: &BigNum| {
+        freshness
+            .valuation
+            .amount(lang, &channel.deposit_asset, amount)
+            .short
+    };
+    let url = format!(
+        "{}/channel/{}/status",
+        &channel.spec.validators.leader().url,
+        channel.id
+    );
+    let earners = channel
+        .status
+        .balances
+        .iter()
+        .filter(|(earner, _)| !earner.eq_ignore_ascii_case(&channel.creator))
+        .sorted_by(|(_, x), (_, y)| y.cmp(x))
+        .map(|(earner, balance)| {
+            li![
+                watchlist::star(lang, watchlist, watchlist::WatchKind::Publisher, earner),
+                format!("{}: {}", earner, amount(balance))
+            ]
+        })
+        .collect::<Vec<_>>();
+    div![
+        h2![
+            watchlist::star(lang, watchlist, watchlist::WatchKind::Channel, &channel.id),
+            lang.tf("Channel {}", &[&channel.id])
+        ],
+        p![
+            watchlist::star(lang, watchlist, watchlist::WatchKind::Advertiser, &channel.creator),
+            lang.tf("Created by {}", &[&channel.creator]),
+            br![],
+            lang.tf(
+                "{}, {} paid out of {}",
+                &[
+                    &lang.t(channel.status.status_type.name()),
+                    &amount(&channel.status.balances_sum()),
+                    &amount(&channel.deposit_amount)
+                ]
+            ),
+            br![],
+            format!("{}: ", lang.t("Created")),
+            relative_time(
+                freshness,
+                &channel.spec.created,
+                lang.time_ago(freshness.zone, freshness.now, &channel.spec.created)
+            ),
+            format!(", {}: ", lang.t("Withdraw period")),
+            relative_time(
+                freshness,
+                &channel.spec.withdraw_period_start,
+                lifecycle::countdown(lang, freshness.now, &channel.spec.withdraw_period_start)
+            ),
+            format!(", {}: ", lang.t("Expires")),
+            relative_time(
+                freshness,
+                &channel.valid_until,
+                lifecycle::countdown(lang, freshness.now, &channel.valid_until)
+            ),
+            br![],
+            lifecycle::lifecycle_timeline(lang, freshness.zone, freshness.now, channel),
+            br![],
+            a![
+                attrs! {At::Href => url; At::Target => "_blank"},
+                lang.t("Validator status")
+            ],
+        ],
+        if earners.is_empty() {
+            seed::empty()
+        } else {
+            div![h3![lang.t("Publishers")], ul![earners]]
+        },
+        h3![lang.t("Status history")],
+        status_history::status_timeline(lang, freshness.zone, freshness.now, statuses),
+        targeting::channel_targeting(lang, channel),
+    ]
+}
+
 fn unit_preview(unit: &AdUnit) -> Node<Msg> {
     if unit.media_mime.starts_with("video/") {
         video![
@@ -492,23 +1417,28 @@ fn to_http_url(url: &str) -> String {
     }
 }
 
-fn time_diff(now_seconds: i64, t: &DateTime<Utc>) -> String {
-    let time_diff = now_seconds - t.timestamp();
-    match time_diff {
-        x if x < 0 => "just now".to_string(),
-        x if x < 60 => format!("{} seconds ago", x),
-        x if x < 3600 => format!("{} minutes ago", x / 60),
-        x if x < 86400 => format!("{} hours ago", x / 3600),
-        _ => format!("{}", t.format("%Y-%m-%d")), // %T if we want time
-    }
+fn now_seconds() -> i64 {
+    (js_sys::Date::now() as i64) / 1000
 }
 
-fn dai_readable(bal: &BigNum) -> String {
-    // 10 ** 16`
-    match bal.div_floor(&10_000_000_000_000_000u64.into()).to_f64() {
-        Some(hundreds) => format!("{:.2} DAI", hundreds / 100.0),
-        None => ">max".to_owned(),
+// N/A for campaigns without a deposit, which the market may still list
+pub fn paid_percentage(paid: &BigNum, deposit: &BigNum) -> String {
+    if *deposit == BigNum::from(0) {
+        return "N/A".to_string();
     }
+    let base = 100_000_u64;
+    let paid_units = (paid * &base.into()).div_floor(deposit);
+    let paid_hundreds = paid_units.to_f64().unwrap_or(base as f64) / (base as f64 / 100.0);
+    format!("{:.3}%", paid_hundreds)
+}
+
+// In English, for the CLI, the alerts and the exports
+pub fn dai_readable(bal: &BigNum) -> String {
+    dai_readable_precision(bal, 2)
+}
+
+pub fn dai_readable_precision(bal: &BigNum, precision: u32) -> String {
+    Language::English.dai_precision(bal, precision)
 }
 
 // Router
@@ -516,6 +1446,19 @@ fn routes(url: seed::Url) -> Msg {
     match url.path.get(0).map(|x| x.as_ref()) {
         Some("channels") => Msg::Load(ActionLoad::ChannelsActive),
         Some("channels-all") => Msg::Load(ActionLoad::ChannelsAll),
+        Some("ad-type") => match url.path.get(1) {
+            Some(ad_type) => Msg::Load(ActionLoad::ChannelsByAdType(ad_type.to_string())),
+            None => Msg::Load(ActionLoad::Summary),
+        },
+        Some("targeting") => Msg::Load(ActionLoad::Targeting),
+        Some("alerts") => Msg::Load(ActionLoad::Alerts),
+        Some("watchlist") => Msg::Load(ActionLoad::Watchlist),
+        Some("expiring") => Msg::Load(ActionLoad::ChannelsExpiring(
+            url.path
+                .get(1)
+                .and_then(|days| days.parse().ok())
+                .unwrap_or(DEFAULT_EXPIRING_DAYS),
+        )),
         Some("channel") => match url.path.get(1) {
             Some(id) => Msg::Load(ActionLoad::ChannelDetail(id.to_string())),
             None => Msg::Load(ActionLoad::Summary),
@@ -528,8 +1471,66 @@ fn routes(url: seed::Url) -> Msg {
 pub fn render() {
     let state = seed::App::build(
         |url, orders| {
+            let search = url.search.clone().unwrap_or_default();
+            let endpoints = Endpoints::from_search(&search);
+            let demo = DemoConfig::from_search(&search);
             orders.send_msg(routes(url));
-            Model::default()
+            let schedule = refresh::Schedule {
+                rate: cache::read(REFRESH_RATE_KEY).unwrap_or_default(),
+                ..Default::default()
+            };
+            let staleness_threshold =
+                cache::read(STALENESS_THRESHOLD_KEY).unwrap_or(DEFAULT_STALENESS_THRESHOLD);
+            let language = cache::read(LANGUAGE_KEY).unwrap_or_default();
+            let zone = cache::read(ZONE_KEY).unwrap_or_default();
+            let currency = cache::read(CURRENCY_KEY).unwrap_or_default();
+            // Demo mode starts from scratch, to show the loading states; so do overridden endpoints,
+            // which shouldn't be mixed with what was cached from the real ones
+            if demo.is_some() || endpoints != Endpoints::default() {
+                return Model {
+                    now: now_seconds(),
+                    schedule,
+                    staleness_threshold,
+                    language,
+                    zone,
+                    currency,
+                    endpoints,
+                    alert_config: alerts_page::load(),
+                    watchlist: watchlist::load(),
+                    demo,
+                    ..Model::default()
+                };
+            }
+            let history = history::load();
+            // Render whatever we have cached until fresh data arrives
+            let market_channels = cache::load(CHANNELS_CACHE);
+            let last_loaded = match &market_channels {
+                Stale(_, time) => *time,
+                _ => 0,
+            };
+            Model {
+                now: now_seconds(),
+                market_channels,
+                last_loaded,
+                balance: cache::load(BALANCE_CACHE),
+                volume: cache::load(VOLUME_CACHE),
+                impressions: cache::load(IMPRESSIONS_CACHE),
+                daily_impressions: cache::load(DAILY_IMPRESSIONS_CACHE),
+                yearly_impressions: cache::load(YEARLY_IMPRESSIONS_CACHE),
+                prices: cache::load(PRICES_CACHE),
+                status_history: status_history::load(),
+                previous_visit: history.last().cloned(),
+                history,
+                schedule,
+                staleness_threshold,
+                language,
+                zone,
+                currency,
+                endpoints,
+                alert_config: alerts_page::load(),
+                watchlist: watchlist::load(),
+                ..Model::default()
+            }
         },
         update,
         view,
@@ -538,5 +1539,19 @@ pub fn render() {
     .finish()
     .run();
 
-    seed::set_interval(Box::new(move || state.update(Msg::Refresh)), REFRESH_MS);
+    seed::set_interval(Box::new(move || state.update(Msg::Tick)), TICK_MS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paid_percentage_of_empty_deposits() {
+        let dai = BigNum::from(1_000_000_000_000_000_000u64);
+        assert_eq!(paid_percentage(&BigNum::from(0), &BigNum::from(0)), "N/A");
+        assert_eq!(paid_percentage(&dai, &BigNum::from(0)), "N/A");
+        assert_eq!(paid_percentage(&BigNum::from(0), &dai), "0.000%");
+        assert_eq!(paid_percentage(&dai, &(&dai * &BigNum::from(8))), "12.500%");
+    }
 }
@@ -0,0 +1,188 @@
+use super::{i18n, types, Msg};
+
+use chrono::{DateTime, Utc};
+use i18n::{Language, Zone};
+use seed::prelude::*;
+use types::MarketChannel;
+
+// The stages a campaign goes through, in order
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Created,
+    Active,
+    WithdrawPeriod,
+    Expired,
+}
+
+impl Stage {
+    // The English name, to be translated
+    pub fn name(self) -> &'static str {
+        match self {
+            Stage::Created => "created",
+            Stage::Active => "active",
+            Stage::WithdrawPeriod => "in the withdraw period",
+            Stage::Expired => "expired",
+        }
+    }
+}
+
+pub fn stage(now_seconds: i64, channel: &MarketChannel) -> Stage {
+    let active_from = channel.spec.active_from.unwrap_or(channel.spec.created);
+    match now_seconds {
+        x if x >= channel.valid_until.timestamp() => Stage::Expired,
+        x if x >= channel.spec.withdraw_period_start.timestamp() => Stage::WithdrawPeriod,
+        x if x >= active_from.timestamp() => Stage::Active,
+        _ => Stage::Created,
+    }
+}
+
+// Channels which will enter their withdraw period within the next `days`
+pub fn is_expiring_within(now_seconds: i64, days: i64, channel: &MarketChannel) -> bool {
+    let until_withdraw = channel.spec.withdraw_period_start.timestamp() - now_seconds;
+    until_withdraw >= 0 && until_withdraw <= days * 86400
+}
+
+pub fn countdown(lang: Language, now_seconds: i64, t: &DateTime<Utc>) -> String {
+    let time_left = t.timestamp() - now_seconds;
+    match time_left {
+        x if x <= 0 => lang.t("passed").to_string(),
+        x if x < 60 => lang.tn("in {} second", "in {} seconds", x),
+        x if x < 3600 => lang.tn("in {} minute", "in {} minutes", x / 60),
+        x if x < 86400 => lang.tn("in {} hour", "in {} hours", x / 3600),
+        x => lang.tn("in {} day", "in {} days", x / 86400),
+    }
+}
+
+pub fn lifecycle_timeline(
+    lang: Language,
+    zone: Zone,
+    now_seconds: i64,
+    channel: &MarketChannel,
+) -> Node<Msg> {
+    let created = channel.spec.created.timestamp();
+    let active_from = channel
+        .spec
+        .active_from
+        .unwrap_or(channel.spec.created)
+        .timestamp();
+    let withdraw_start = channel.spec.withdraw_period_start.timestamp();
+    let valid_until = channel.valid_until.timestamp();
+
+    let width = 120_f64;
+    let height = 12_f64;
+    let span = (valid_until - created).max(1) as f64;
+    let x_of = |t: i64| ((t - created).max(0) as f64 / span * width).min(width);
+
+    let segment = |from: i64, to: i64, color: &str| {
+        rect![attrs! {
+            At::Custom("x".into()) => format!("{:.1}", x_of(from));
+            At::Custom("y".into()) => "0";
+            At::Width => format!("{:.1}", (x_of(to) - x_of(from)).max(0.0));
+            At::Height => format!("{}", height);
+            At::Fill => color;
+        }]
+    };
+
+    let current_stage = stage(now_seconds, channel);
+    svg![
+        attrs! {
+            At::Width => format!("{}px", width);
+            At::Height => format!("{}px", height);
+            At::ViewBox => format!("0 0 {} {}", width, height);
+        },
+        title![lang.tf(
+            "Created {}, active from {}, withdraw period {}, expires {} ({})",
+            &[
+                &lang.date(zone, &channel.spec.created),
+                &lang.date(zone, &channel.spec.active_from.unwrap_or(channel.spec.created)),
+                &lang.date(zone, &channel.spec.withdraw_period_start),
+                &lang.date(zone, &channel.valid_until),
+                &lang.t(current_stage.name()),
+            ]
+        )],
+        segment(created, active_from, "#efefef"),
+        segment(active_from, withdraw_start, "#14dc9d"),
+        segment(withdraw_start, valid_until, "#fabb5a"),
+        line_![attrs! {
+            At::Custom("x1".into()) => format!("{:.1}", x_of(now_seconds));
+            At::Custom("x2".into()) => format!("{:.1}", x_of(now_seconds));
+            At::Custom("y1".into()) => "0";
+            At::Custom("y2".into()) => format!("{}", height);
+            At::Custom("stroke".into()) => "#003159";
+            At::Custom("stroke-width".into()) => "2";
+        }],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_fixtures::fixture;
+    use chrono::TimeZone;
+
+    const DAY: i64 = 86400;
+
+    // Created at 0, active from 100, in its withdraw period from 1000, expired from 2000
+    fn channel() -> MarketChannel {
+        let mut channel = fixture().remove(0);
+        channel.spec.created = Utc.timestamp(0, 0);
+        channel.spec.active_from = Some(Utc.timestamp(100, 0));
+        channel.spec.withdraw_period_start = Utc.timestamp(1000, 0);
+        channel.valid_until = Utc.timestamp(2000, 0);
+        channel
+    }
+
+    #[test]
+    fn stages() {
+        let channel = channel();
+        let stages = [-1, 0, 99, 100, 999, 1000, 1999, 2000]
+            .iter()
+            .map(|t| stage(*t, &channel))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stages,
+            vec![
+                Stage::Created,
+                Stage::Created,
+                Stage::Created,
+                Stage::Active,
+                Stage::Active,
+                Stage::WithdrawPeriod,
+                Stage::WithdrawPeriod,
+                Stage::Expired,
+            ]
+        );
+
+        // active as soon as it's created, unless said otherwise
+        let mut channel = channel;
+        channel.spec.active_from = None;
+        assert_eq!(stage(0, &channel), Stage::Active);
+    }
+
+    #[test]
+    fn expiring_within() {
+        let channel = channel();
+        assert!(is_expiring_within(1000 - DAY, 1, &channel));
+        assert!(!is_expiring_within(1000 - DAY - 1, 1, &channel));
+        assert!(is_expiring_within(1000 - DAY - 1, 2, &channel));
+        assert!(is_expiring_within(1000, 1, &channel));
+        // already in its withdraw period
+        assert!(!is_expiring_within(1001, 1, &channel));
+    }
+
+    #[test]
+    fn countdowns() {
+        let t = Utc.timestamp(1000 + 2 * DAY, 0);
+        let at = |seconds_left: i64| countdown(Language::English, t.timestamp() - seconds_left, &t);
+        assert_eq!(at(-5), "passed");
+        assert_eq!(at(0), "passed");
+        assert_eq!(at(1), "in 1 second");
+        assert_eq!(at(59), "in 59 seconds");
+        assert_eq!(at(60), "in 1 minute");
+        assert_eq!(at(3599), "in 59 minutes");
+        assert_eq!(at(3600), "in 1 hour");
+        assert_eq!(at(DAY - 1), "in 23 hours");
+        assert_eq!(at(DAY), "in 1 day");
+        assert_eq!(at(2 * DAY + 5), "in 2 days");
+    }
+}
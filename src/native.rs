@@ -0,0 +1,85 @@
+// Helpers for the native binaries in `src/bin`, which run outside of the browser
+use super::endpoints::Endpoints;
+use super::refresh::Source;
+use super::types::{AnalyticsResp, EtherscanBalResp, MarketChannel, PricesResp};
+
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::env;
+use std::io::Read;
+
+// Everything the summary is computed from, as fetched at `time` (in seconds)
+pub struct MarketData {
+    pub time: i64,
+    pub channels: Vec<MarketChannel>,
+    pub balance: EtherscanBalResp,
+    pub volume: AnalyticsResp,
+    pub impressions: AnalyticsResp,
+    pub daily_impressions: AnalyticsResp,
+    pub yearly_impressions: AnalyticsResp,
+    // `None` when the price source is unreachable: the static prices are used instead
+    pub prices: Option<PricesResp>,
+}
+
+// The default endpoints, overridden by `ADEX_MARKET_URL`, `ADEX_ANALYTICS_URL`, `ADEX_ETHERSCAN_URL`
+// and `ADEX_PRICES_URL`
+pub fn endpoints_from_env() -> Endpoints {
+    let default = Endpoints::default();
+    let endpoint = |name: &str, default: String| {
+        env::var(name)
+            .ok()
+            .filter(|url| !url.is_empty())
+            .map(|url| url.trim_end_matches('/').to_string())
+            .unwrap_or(default)
+    };
+    Endpoints {
+        market: endpoint("ADEX_MARKET_URL", default.market),
+        analytics: endpoint("ADEX_ANALYTICS_URL", default.analytics),
+        etherscan: endpoint("ADEX_ETHERSCAN_URL", default.etherscan),
+        prices: endpoint("ADEX_PRICES_URL", default.prices),
+    }
+}
+
+pub fn fetch_text(url: &str) -> Result<String, String> {
+    let mut resp = reqwest::get(url).map_err(|e| format!("{}: {}", url, e))?;
+    if !resp.status().is_success() {
+        return Err(format!("{}: {}", url, resp.status()));
+    }
+    let mut body = String::new();
+    resp.read_to_string(&mut body)
+        .map_err(|e| format!("{}: {}", url, e))?;
+    Ok(body)
+}
+
+pub fn fetch_json<T: DeserializeOwned>(url: &str) -> Result<T, String> {
+    serde_json::from_str(&fetch_text(url)?).map_err(|e| format!("{}: {}", url, e))
+}
+
+// The same sources as the summary page of the explorer
+pub fn fetch_market_data(endpoints: &Endpoints) -> Result<MarketData, String> {
+    Ok(MarketData {
+        time: Utc::now().timestamp(),
+        channels: fetch_json(&endpoints.source_url(Source::Channels))?,
+        balance: fetch_json(&endpoints.source_url(Source::Balance))?,
+        volume: fetch_json(&endpoints.source_url(Source::Volume))?,
+        impressions: fetch_json(&endpoints.source_url(Source::Impressions))?,
+        daily_impressions: fetch_json(&endpoints.source_url(Source::DailyImpressions))?,
+        yearly_impressions: fetch_json(&endpoints.source_url(Source::YearlyImpressions))?,
+        prices: fetch_json(&endpoints.source_url(Source::Prices)).ok(),
+    })
+}
+
+pub fn post_json<T: Serialize>(url: &str, data: &T) -> Result<(), String> {
+    let body = serde_json::to_string(data).map_err(|e| e.to_string())?;
+    let resp = reqwest::Client::new()
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .map_err(|e| format!("{}: {}", url, e))?;
+    if !resp.status().is_success() {
+        return Err(format!("{}: {}", url, resp.status()));
+    }
+    Ok(())
+}
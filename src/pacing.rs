@@ -0,0 +1,197 @@
+use super::types;
+
+use adex_domain::BigNum;
+use chrono::{DateTime, TimeZone, Utc};
+use std::collections::HashMap;
+use types::MarketChannel;
+
+// How far apart spent and elapsed time can be before we flag a campaign
+const PACING_TOLERANCE: f64 = 0.1;
+
+// The first paid amount we've seen for a channel since the explorer was opened
+pub struct PaidObservation {
+    pub time: i64,
+    pub paid: BigNum,
+}
+
+pub type PaidObservations = HashMap<String, PaidObservation>;
+
+pub fn observe(observations: &mut PaidObservations, now_seconds: i64, channels: &[MarketChannel]) {
+    for channel in channels {
+        observations
+            .entry(channel.id.clone())
+            .or_insert_with(|| PaidObservation {
+                time: now_seconds,
+                paid: channel.status.balances_sum(),
+            });
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PacingStatus {
+    UnderPacing,
+    OnPace,
+    OverPacing,
+}
+
+impl PacingStatus {
+    // The English name, to be translated
+    pub fn name(self) -> &'static str {
+        match self {
+            PacingStatus::UnderPacing => "Under-pacing",
+            PacingStatus::OnPace => "On pace",
+            PacingStatus::OverPacing => "Over-pacing",
+        }
+    }
+}
+
+pub struct Pacing {
+    pub spent: f64,
+    pub elapsed: f64,
+    pub status: PacingStatus,
+    pub projected_exhaustion: Option<DateTime<Utc>>,
+}
+
+pub fn pacing(
+    now_seconds: i64,
+    observations: &PaidObservations,
+    channel: &MarketChannel,
+) -> Option<Pacing> {
+    let deposit = channel.deposit_amount.to_f64()?;
+    if deposit <= 0.0 {
+        return None;
+    }
+    let paid = channel.status.balances_sum();
+    let paid_f = paid.to_f64()?;
+
+    let start = channel
+        .spec
+        .active_from
+        .unwrap_or(channel.spec.created)
+        .timestamp();
+    let end = channel.spec.withdraw_period_start.timestamp();
+    let duration = (end - start).max(1) as f64;
+
+    let spent = paid_f / deposit;
+    let elapsed = ((now_seconds - start) as f64 / duration).max(0.0).min(1.0);
+    let status = match spent - elapsed {
+        x if x > PACING_TOLERANCE => PacingStatus::OverPacing,
+        x if x < -PACING_TOLERANCE => PacingStatus::UnderPacing,
+        _ => PacingStatus::OnPace,
+    };
+
+    Some(Pacing {
+        spent,
+        elapsed,
+        status,
+        projected_exhaustion: burn_rate(now_seconds, start, observations, channel, &paid)
+            .and_then(|per_second| now_seconds.checked_add(((deposit - paid_f) / per_second) as i64))
+            .and_then(|t| Utc.timestamp_opt(t, 0).single()),
+    })
+}
+
+// Paid per second: from what we've observed across refreshes if anything changed,
+// otherwise the average since the campaign started
+fn burn_rate(
+    now_seconds: i64,
+    start: i64,
+    observations: &PaidObservations,
+    channel: &MarketChannel,
+    paid: &BigNum,
+) -> Option<f64> {
+    let observed = observations.get(&channel.id).and_then(|first| {
+        let period = now_seconds - first.time;
+        if period > 0 && paid > &first.paid {
+            Some((paid - &first.paid).to_f64()? / period as f64)
+        } else {
+            None
+        }
+    });
+    let rate = match observed {
+        Some(rate) => rate,
+        None if now_seconds > start => paid.to_f64()? / (now_seconds - start) as f64,
+        None => return None,
+    };
+    if rate > 0.0 {
+        Some(rate)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_fixtures::{dai, fixture};
+
+    // 400 of its 1000 DAI paid, active from 0 to 1000
+    fn channel() -> MarketChannel {
+        let mut channel = fixture().remove(0);
+        channel.spec.active_from = Some(Utc.timestamp(0, 0));
+        channel.spec.withdraw_period_start = Utc.timestamp(1000, 0);
+        channel
+    }
+
+    fn status_at(now_seconds: i64, channel: &MarketChannel) -> Option<PacingStatus> {
+        pacing(now_seconds, &PaidObservations::new(), channel).map(|x| x.status)
+    }
+
+    #[test]
+    fn on_pace() {
+        let channel = channel();
+        let pacing = pacing(400, &PaidObservations::new(), &channel).unwrap();
+        assert_eq!(pacing.status, PacingStatus::OnPace);
+        assert!((pacing.spent - 0.4).abs() < 1e-9);
+        assert!((pacing.elapsed - 0.4).abs() < 1e-9);
+        // 1 DAI a second on average, so the other 600 last until the end
+        assert_eq!(pacing.projected_exhaustion, Some(Utc.timestamp(1000, 0)));
+        // within the tolerance
+        assert_eq!(status_at(450, &channel), Some(PacingStatus::OnPace));
+    }
+
+    #[test]
+    fn under_pacing() {
+        assert_eq!(status_at(800, &channel()), Some(PacingStatus::UnderPacing));
+    }
+
+    #[test]
+    fn over_pacing() {
+        assert_eq!(status_at(100, &channel()), Some(PacingStatus::OverPacing));
+        // before it's active, anything paid is ahead of time
+        assert_eq!(status_at(-100, &channel()), Some(PacingStatus::OverPacing));
+    }
+
+    #[test]
+    fn observed_burn_rate() {
+        let mut channel = channel();
+        let mut observations = PaidObservations::new();
+        observe(&mut observations, 400, &[channel.clone()]);
+        // 100 DAI in 50 seconds since then
+        let earner = channel.status.balances.keys().next().cloned().unwrap();
+        channel.status.balances.insert(earner, dai(500));
+        let pacing = pacing(450, &observations, &channel).unwrap();
+        assert_eq!(pacing.projected_exhaustion, Some(Utc.timestamp(700, 0)));
+    }
+
+    #[test]
+    fn zero_deposit() {
+        let mut channel = channel();
+        channel.deposit_amount = dai(0);
+        assert!(pacing(400, &PaidObservations::new(), &channel).is_none());
+    }
+
+    #[test]
+    fn expired() {
+        let channel = channel();
+        let pacing = pacing(2000, &PaidObservations::new(), &channel).unwrap();
+        // the elapsed time stops at the end
+        assert!((pacing.elapsed - 1.0).abs() < 1e-9);
+        assert_eq!(pacing.status, PacingStatus::UnderPacing);
+
+        // fully paid out by the end
+        let mut exhausted = fixture().remove(2);
+        exhausted.spec.active_from = Some(Utc.timestamp(0, 0));
+        exhausted.spec.withdraw_period_start = Utc.timestamp(1000, 0);
+        assert_eq!(status_at(2000, &exhausted), Some(PacingStatus::OnPace));
+    }
+}
@@ -0,0 +1,192 @@
+// USD valuation of the deposit assets, at the prices of the price source (CoinGecko by default),
+// or at static ones until it responds or when it's unreachable
+use super::endpoints::{DAI_ADDR, SAI_ADDR};
+use super::i18n::{Compact, Language};
+use super::types::PricesResp;
+
+use adex_domain::BigNum;
+use serde::{Deserialize, Serialize};
+
+// Both are meant to track the dollar
+const STATIC_PRICES: [(&str, f64); 2] = [(DAI_ADDR, 1.0), (SAI_ADDR, 1.0)];
+// Prices are applied with 6 decimals, so that amounts keep their precision
+const PRICE_SCALE: u64 = 1_000_000;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Currency {
+    Dai,
+    Usd,
+    Both,
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Currency::Dai
+    }
+}
+
+impl Currency {
+    pub const ALL: [Currency; 3] = [Currency::Dai, Currency::Usd, Currency::Both];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Currency::Dai => "dai",
+            Currency::Usd => "usd",
+            Currency::Both => "both",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Currency> {
+        Currency::ALL.iter().cloned().find(|currency| currency.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Currency::Dai => "Amounts in DAI",
+            Currency::Usd => "Amounts in USD",
+            Currency::Both => "Amounts in DAI and USD",
+        }
+    }
+}
+
+// The USD price of a deposit asset; `None` for assets we know nothing about
+pub fn usd_price(prices: Option<&PricesResp>, asset: &str) -> Option<f64> {
+    prices
+        .and_then(|prices| prices.get(&asset.to_lowercase()))
+        .map(|price| price.usd)
+        .or_else(|| {
+            STATIC_PRICES
+                .iter()
+                .find(|(addr, _)| addr.eq_ignore_ascii_case(asset))
+                .map(|(_, price)| *price)
+        })
+}
+
+// With as many decimals as the amount
+pub fn usd_value(amount: &BigNum, price: f64) -> BigNum {
+    let scaled_price = (price.max(0.0) * PRICE_SCALE as f64).round() as u64;
+    (amount * &BigNum::from(scaled_price)).div_floor(&BigNum::from(PRICE_SCALE))
+}
+
+// How amounts are shown, and at which prices
+#[derive(Clone, Copy)]
+pub struct Valuation<'a> {
+    pub currency: Currency,
+    pub prices: Option<&'a PricesResp>,
+}
+
+impl<'a> Valuation<'a> {
+    pub fn usd(self, asset: &str, amount: &BigNum) -> Option<BigNum> {
+        usd_price(self.prices, asset).map(|price| usd_value(amount, price))
+    }
+
+    // Amounts in several assets; unknown if any of them can't be priced
+    pub fn usd_total<'b>(
+        self,
+        amounts: impl Iterator<Item = (&'b str, BigNum)>,
+    ) -> Option<BigNum> {
+        amounts
+            .map(|(asset, amount)| self.usd(asset, &amount))
+            .sum::<Option<BigNum>>()
+    }
+
+    // Rounded to the cent
+    pub fn amount(self, lang: Language, asset: &str, amount: &BigNum) -> Compact {
+        self.amount_with_usd(lang, amount, self.usd(asset, amount))
+    }
+
+    pub fn amount_with_usd(
+        self,
+        lang: Language,
+        amount: &BigNum,
+        usd: Option<BigNum>,
+    ) -> Compact {
+        let dai = Compact {
+            short: lang.dai(amount),
+            full: lang.dai_full(amount),
+        };
+        let usd = usd.map(|usd| Compact {
+            short: lang.usd(&usd),
+            full: lang.usd_full(&usd),
+        });
+        self.show(dai, usd)
+    }
+
+    pub fn compact(self, lang: Language, asset: &str, amount: &BigNum) -> Compact {
+        self.compact_with_usd(lang, amount, self.usd(asset, amount))
+    }
+
+    // For totals which were valued asset by asset
+    pub fn compact_with_usd(
+        self,
+        lang: Language,
+        amount: &BigNum,
+        usd: Option<BigNum>,
+    ) -> Compact {
+        self.show(lang.compact_dai(amount), usd.map(|usd| lang.compact_usd(&usd)))
+    }
+
+    // Amounts which can't be priced are only shown in DAI
+    fn show(self, dai: Compact, usd: Option<Compact>) -> Compact {
+        match (self.currency, usd) {
+            (Currency::Dai, _) | (_, None) => dai,
+            (Currency::Usd, Some(usd)) => Compact {
+                short: usd.short,
+                full: format!("{} ({})", usd.full, dai.full),
+            },
+            (Currency::Both, Some(usd)) => Compact {
+                short: format!("{} ({})", dai.short, usd.short),
+                full: format!("{} ({})", dai.full, usd.full),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_fixtures::dai;
+    use super::super::types::TokenPrice;
+
+    #[test]
+    fn falls_back_to_static_prices() {
+        let mut prices = PricesResp::new();
+        prices.insert(DAI_ADDR.to_lowercase(), TokenPrice { usd: 1.02 });
+        assert_eq!(usd_price(Some(&prices), DAI_ADDR), Some(1.02));
+        assert_eq!(usd_price(Some(&prices), SAI_ADDR), Some(1.0));
+        assert_eq!(usd_price(None, &DAI_ADDR.to_lowercase()), Some(1.0));
+        assert_eq!(usd_price(Some(&prices), "0x0"), None);
+    }
+
+    #[test]
+    fn values_exactly() {
+        assert_eq!(usd_value(&dai(1_000), 1.0), dai(1_000));
+        assert_eq!(usd_value(&dai(1_000), 1.0125), dai(10_125).div_floor(&BigNum::from(10)));
+        // Past u64 and f64 precision
+        let huge = &BigNum::from(u64::max_value()) * &dai(1);
+        assert_eq!(usd_value(&huge, 2.0), &huge * &BigNum::from(2));
+    }
+
+    #[test]
+    fn shows_the_chosen_currency() {
+        let mut prices = PricesResp::new();
+        prices.insert(DAI_ADDR.to_lowercase(), TokenPrice { usd: 1.5 });
+        let valuation = |currency| Valuation {
+            currency,
+            prices: Some(&prices),
+        };
+        let amount = |currency, asset| {
+            valuation(currency)
+                .amount(Language::English, asset, &dai(10))
+                .short
+        };
+        assert_eq!(amount(Currency::Dai, DAI_ADDR), "10.00 DAI");
+        assert_eq!(amount(Currency::Usd, DAI_ADDR), "15.00 USD");
+        assert_eq!(amount(Currency::Both, DAI_ADDR), "10.00 DAI (15.00 USD)");
+        assert_eq!(amount(Currency::Usd, "0x0"), "10.00 DAI");
+        let total = valuation(Currency::Usd)
+            .usd_total(vec![(DAI_ADDR, dai(10)), (SAI_ADDR, dai(1))].into_iter());
+        assert_eq!(total, Some(dai(16)));
+        assert_eq!(Currency::from_code("both"), Some(Currency::Both));
+    }
+}
@@ -0,0 +1,184 @@
+// The numbers of the summary in the Prometheus text format, for the metrics exporter
+use super::native::MarketData;
+use super::report;
+use super::types::MarketStatusType;
+use super::DAI_DECIMALS;
+
+use adex_domain::BigNum;
+use std::fmt::Write;
+
+// DAI and USD amounts are exported with a precision of 6 decimals
+fn dai(amount: &BigNum) -> f64 {
+    amount
+        .div_floor(&10_u64.pow(DAI_DECIMALS - 6).into())
+        .to_f64()
+        .unwrap_or(std::f64::NAN)
+        / 1_000_000.0
+}
+
+struct Metrics(String);
+
+impl Metrics {
+    fn gauge(&mut self, name: &str, help: &str, samples: &[(&str, f64)]) {
+        // Writing to a String can't fail
+        let _ = writeln!(self.0, "# HELP {} {}", name, help);
+        let _ = writeln!(self.0, "# TYPE {} gauge", name);
+        for (labels, value) in samples {
+            let _ = writeln!(self.0, "{}{} {}", name, labels, value);
+        }
+    }
+}
+
+pub fn metrics(data: &MarketData) -> String {
+    let summary = report::summary(data);
+    let mut metrics = Metrics(String::new());
+
+    metrics.gauge(
+        "adex_campaigns",
+        "Campaigns on the market",
+        &[("", summary.summary.campaigns as f64)],
+    );
+    metrics.gauge(
+        "adex_ad_units",
+        "Distinct ad units across all campaigns",
+        &[("", summary.summary.ad_units as f64)],
+    );
+    metrics.gauge(
+        "adex_publishers",
+        "Publishers who earned from any campaign",
+        &[("", summary.summary.publishers as f64)],
+    );
+    metrics.gauge(
+        "adex_advertisers",
+        "Advertisers who created any campaign",
+        &[("", summary.summary.advertisers as f64)],
+    );
+    metrics.gauge(
+        "adex_total_deposit_dai",
+        "Total campaign deposits, in DAI",
+        &[("", dai(&summary.summary.total_deposit))],
+    );
+    metrics.gauge(
+        "adex_paid_out_dai",
+        "Paid out by all campaigns, in DAI",
+        &[("", dai(&summary.summary.paid_out))],
+    );
+    metrics.gauge(
+        "adex_locked_on_chain_dai",
+        "DAI locked up in the core contract",
+        &[("", dai(&summary.locked_on_chain))],
+    );
+    metrics.gauge(
+        "adex_volume_24h_dai",
+        "Paid out in the last 24 hours, in DAI",
+        &[("", dai(&summary.volume_24h))],
+    );
+    metrics.gauge(
+        "adex_dai_price_usd",
+        "The price of DAI, from the price source or the static fallback",
+        &[("", summary.dai_price_usd.unwrap_or(std::f64::NAN))],
+    );
+    let usd = |amount: &Option<BigNum>| amount.as_ref().map(dai).unwrap_or(std::f64::NAN);
+    metrics.gauge(
+        "adex_total_deposit_usd",
+        "Total campaign deposits, in USD",
+        &[("", usd(&summary.total_deposit_usd))],
+    );
+    metrics.gauge(
+        "adex_paid_out_usd",
+        "Paid out by all campaigns, in USD",
+        &[("", usd(&summary.paid_out_usd))],
+    );
+    metrics.gauge(
+        "adex_locked_on_chain_usd",
+        "DAI locked up in the core contract, in USD",
+        &[("", usd(&summary.locked_on_chain_usd))],
+    );
+    metrics.gauge(
+        "adex_volume_24h_usd",
+        "Paid out in the last 24 hours, in USD",
+        &[("", usd(&summary.volume_24h_usd))],
+    );
+    metrics.gauge(
+        "adex_impressions",
+        "Impressions in the last day, month and year",
+        &[
+            (
+                "{timeframe=\"day\"}",
+                summary.daily_impressions.to_f64().unwrap_or(std::f64::NAN),
+            ),
+            (
+                "{timeframe=\"month\"}",
+                summary.monthly_impressions.to_f64().unwrap_or(std::f64::NAN),
+            ),
+            (
+                "{timeframe=\"year\"}",
+                summary.yearly_impressions.to_f64().unwrap_or(std::f64::NAN),
+            ),
+        ],
+    );
+
+    let status_labels = MarketStatusType::ALL
+        .iter()
+        .map(|status| format!("{{status=\"{:?}\"}}", status))
+        .collect::<Vec<_>>();
+    let by_status = MarketStatusType::ALL
+        .iter()
+        .zip(status_labels.iter())
+        .map(|(status, labels)| {
+            let count = data
+                .channels
+                .iter()
+                .filter(|channel| &channel.status.status_type == status)
+                .count();
+            (labels.as_str(), count as f64)
+        })
+        .collect::<Vec<_>>();
+    metrics.gauge("adex_channels", "Channels by status", &by_status);
+
+    metrics.gauge(
+        "adex_last_update_timestamp_seconds",
+        "When the data was fetched",
+        &[("", data.time as f64)],
+    );
+
+    metrics.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_fixtures::market_data;
+
+    #[test]
+    fn exports_gauges() {
+        let metrics = metrics(&market_data(1_571_400_000));
+        assert!(metrics.contains(
+            "# HELP adex_campaigns Campaigns on the market\n# TYPE adex_campaigns gauge\nadex_campaigns 3\n"
+        ));
+        // Every metric is described, and is a gauge
+        let help = metrics.lines().filter(|line| line.starts_with("# HELP adex_")).count();
+        let gauges = metrics
+            .lines()
+            .filter(|line| line.starts_with("# TYPE adex_") && line.ends_with(" gauge"))
+            .count();
+        assert_eq!(help, gauges);
+        assert!(metrics.contains("adex_channels{status=\"Active\"} 2\n"));
+        assert!(metrics.contains("adex_channels{status=\"Exhausted\"} 1\n"));
+        assert!(metrics.contains("adex_channels{status=\"Unhealthy\"} 0\n"));
+        assert!(metrics.contains("adex_impressions{timeframe=\"day\"} "));
+        assert!(metrics.contains("adex_last_update_timestamp_seconds 1571400000\n"));
+        assert!(!metrics.contains("NaN"));
+    }
+
+    #[test]
+    fn unknown_prices_are_nan() {
+        let mut data = market_data(1_571_400_000);
+        data.channels[0].deposit_asset = "0x0".to_string();
+        let metrics = metrics(&data);
+        assert!(metrics.contains("adex_total_deposit_usd NaN\n"));
+        assert!(metrics.contains("adex_paid_out_usd NaN\n"));
+        // The amounts in DAI don't depend on the prices
+        assert!(!metrics.contains("adex_total_deposit_dai NaN"));
+    }
+}
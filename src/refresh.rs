@@ -0,0 +1,295 @@
+use seed::fetch::RequestController;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// Failing sources are retried at most 2^6 times less often than normal
+const MAX_BACKOFF_EXPONENT: u32 = 6;
+
+// Identifies a single fetch, so that responses to superseded fetches can be dropped
+pub type RequestId = u64;
+
+// Controllers of the fetches in progress, so that they can be aborted once superseded
+pub type InFlight = Rc<RefCell<HashMap<Source, RequestController>>>;
+
+pub trait Abort {
+    fn abort(&self);
+}
+
+impl Abort for RequestController {
+    fn abort(&self) {
+        RequestController::abort(self)
+    }
+}
+
+// Aborts the previous fetch of the source, if it's still in progress
+pub fn supersede<C: Abort>(in_flight: &mut HashMap<Source, C>, source: Source) {
+    if let Some(previous) = in_flight.remove(&source) {
+        previous.abort();
+    }
+}
+
+pub fn abort_all<C: Abort>(in_flight: &mut HashMap<Source, C>) {
+    for (_, controller) in in_flight.drain() {
+        controller.abort();
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Source {
+    Balance,
+    Channels,
+    Volume,
+    Impressions,
+    DailyImpressions,
+    YearlyImpressions,
+    Prices,
+}
+
+impl Source {
+    pub const ALL: [Source; 7] = [
+        Source::Balance,
+        Source::Channels,
+        Source::Volume,
+        Source::Impressions,
+        Source::DailyImpressions,
+        Source::YearlyImpressions,
+        Source::Prices,
+    ];
+
+    // How often the source is refreshed at the normal rate
+    fn interval_ms(self) -> f64 {
+        match self {
+            Source::Channels => 30_000.0,
+            Source::Balance | Source::Volume | Source::DailyImpressions => 60_000.0,
+            Source::Impressions | Source::Prices => 300_000.0,
+            Source::YearlyImpressions => 3_600_000.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum RefreshRate {
+    Off,
+    Fast,
+    Normal,
+    Slow,
+}
+
+impl Default for RefreshRate {
+    fn default() -> Self {
+        RefreshRate::Normal
+    }
+}
+
+impl From<String> for RefreshRate {
+    fn from(rate_name: String) -> Self {
+        match &rate_name as &str {
+            "off" => RefreshRate::Off,
+            "fast" => RefreshRate::Fast,
+            "normal" => RefreshRate::Normal,
+            "slow" => RefreshRate::Slow,
+            _ => RefreshRate::default(),
+        }
+    }
+}
+
+impl RefreshRate {
+    fn multiplier(self) -> Option<f64> {
+        match self {
+            RefreshRate::Off => None,
+            RefreshRate::Fast => Some(0.5),
+            RefreshRate::Normal => Some(1.0),
+            RefreshRate::Slow => Some(4.0),
+        }
+    }
+}
+
+#[derive(Default)]
+struct SourceState {
+    last_attempt: f64,
+    failures: u32,
+    // The only fetch whose response we still accept
+    current_request: Option<RequestId>,
+}
+
+#[derive(Default)]
+pub struct Schedule {
+    pub rate: RefreshRate,
+    sources: HashMap<Source, SourceState>,
+    next_request_id: RequestId,
+}
+
+impl Schedule {
+    pub fn start(&mut self, sources: &[Source], now_ms: f64) -> Vec<(Source, RequestId)> {
+        sources
+            .iter()
+            .map(|source| {
+                self.next_request_id += 1;
+                let state = self.sources.entry(*source).or_default();
+                state.last_attempt = now_ms;
+                state.current_request = Some(self.next_request_id);
+                (*source, self.next_request_id)
+            })
+            .collect()
+    }
+
+    pub fn is_current(&self, source: Source, request_id: RequestId) -> bool {
+        self.sources
+            .get(&source)
+            .and_then(|state| state.current_request)
+            == Some(request_id)
+    }
+
+    // Drop the responses of all fetches in progress, e.g. when navigating away
+    pub fn invalidate(&mut self) {
+        for state in self.sources.values_mut() {
+            state.current_request = None;
+        }
+    }
+
+    pub fn succeeded(&mut self, source: Source) {
+        self.sources.entry(source).or_default().failures = 0;
+    }
+
+    pub fn failed(&mut self, source: Source) {
+        self.sources.entry(source).or_default().failures += 1;
+    }
+
+    pub fn reset_backoff(&mut self) {
+        for state in self.sources.values_mut() {
+            state.failures = 0;
+        }
+    }
+
+    // The sources which should be fetched again by now
+    pub fn due(&self, sources: &[Source], now_ms: f64) -> Vec<Source> {
+        let multiplier = match self.rate.multiplier() {
+            Some(multiplier) => multiplier,
+            None => return vec![],
+        };
+        sources
+            .iter()
+            .filter(|source| match self.sources.get(*source) {
+                Some(state) => {
+                    let backoff = 2_u32.pow(state.failures.min(MAX_BACKOFF_EXPONENT)) as f64;
+                    now_ms - state.last_attempt >= source.interval_ms() * multiplier * backoff
+                }
+                None => true,
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    const MINUTE: f64 = 60_000.0;
+
+    struct Controller(Rc<Cell<bool>>);
+
+    impl Abort for Controller {
+        fn abort(&self) {
+            self.0.set(true);
+        }
+    }
+
+    #[test]
+    fn due() {
+        let mut schedule = Schedule::default();
+        assert_eq!(schedule.due(&[Source::Balance], 0.0), vec![Source::Balance]);
+
+        schedule.start(&[Source::Balance, Source::Channels], 0.0);
+        assert_eq!(
+            schedule.due(&[Source::Balance, Source::Channels], MINUTE / 2.0),
+            vec![Source::Channels]
+        );
+        assert!(schedule.due(&[Source::Balance], MINUTE - 1.0).is_empty());
+        // the sources which were never fetched are always due
+        assert_eq!(schedule.due(&Source::ALL, MINUTE).len(), 7);
+
+        schedule.rate = RefreshRate::Slow;
+        assert!(schedule.due(&[Source::Balance], 4.0 * MINUTE - 1.0).is_empty());
+        assert_eq!(schedule.due(&[Source::Balance], 4.0 * MINUTE), vec![Source::Balance]);
+
+        schedule.rate = RefreshRate::Off;
+        assert!(schedule.due(&Source::ALL, 100.0 * MINUTE).is_empty());
+    }
+
+    #[test]
+    fn backoff() {
+        let mut schedule = Schedule::default();
+        schedule.start(&[Source::Balance], 0.0);
+        for _ in 0..3 {
+            schedule.failed(Source::Balance);
+        }
+        // 2^3 times the interval
+        assert!(schedule.due(&[Source::Balance], 8.0 * MINUTE - 1.0).is_empty());
+        assert_eq!(schedule.due(&[Source::Balance], 8.0 * MINUTE).len(), 1);
+
+        // up to 2^6 times
+        for _ in 0..10 {
+            schedule.failed(Source::Balance);
+        }
+        assert!(schedule.due(&[Source::Balance], 64.0 * MINUTE - 1.0).is_empty());
+        assert_eq!(schedule.due(&[Source::Balance], 64.0 * MINUTE).len(), 1);
+
+        schedule.succeeded(Source::Balance);
+        assert_eq!(schedule.due(&[Source::Balance], MINUTE).len(), 1);
+
+        schedule.failed(Source::Balance);
+        schedule.reset_backoff();
+        assert_eq!(schedule.due(&[Source::Balance], MINUTE).len(), 1);
+    }
+
+    #[test]
+    fn is_current() {
+        let mut schedule = Schedule::default();
+        let first = schedule.start(&[Source::Balance, Source::Channels], 0.0);
+        assert!(first.iter().all(|(source, id)| schedule.is_current(*source, *id)));
+        assert!(!schedule.is_current(Source::Volume, first[0].1));
+        assert!(!schedule.is_current(Source::Channels, first[0].1));
+
+        // a new fetch of the balance supersedes the first one, but not the one of the channels
+        let second = schedule.start(&[Source::Balance], MINUTE);
+        assert_ne!(first[0].1, second[0].1);
+        assert!(!schedule.is_current(Source::Balance, first[0].1));
+        assert!(schedule.is_current(Source::Balance, second[0].1));
+        assert!(schedule.is_current(Source::Channels, first[1].1));
+    }
+
+    #[test]
+    fn invalidate() {
+        let mut schedule = Schedule::default();
+        let requests = schedule.start(&Source::ALL, 0.0);
+        schedule.invalidate();
+        assert!(requests.iter().all(|(source, id)| !schedule.is_current(*source, *id)));
+
+        let (source, id) = schedule.start(&[Source::Prices], 0.0)[0];
+        assert!(schedule.is_current(source, id));
+    }
+
+    #[test]
+    fn aborts() {
+        let balance = Rc::new(Cell::new(false));
+        let channels = Rc::new(Cell::new(false));
+        let mut in_flight = HashMap::new();
+        in_flight.insert(Source::Balance, Controller(balance.clone()));
+        in_flight.insert(Source::Channels, Controller(channels.clone()));
+
+        supersede(&mut in_flight, Source::Balance);
+        assert!(balance.get());
+        assert!(!channels.get());
+        assert!(!in_flight.contains_key(&Source::Balance));
+        // nothing in progress to abort
+        supersede(&mut in_flight, Source::Volume);
+
+        abort_all(&mut in_flight);
+        assert!(channels.get());
+        assert!(in_flight.is_empty());
+    }
+}
@@ -0,0 +1,139 @@
+// A static version of the explorer, for crawlers and for browsers without WebAssembly
+use super::*;
+
+use html::{escape, to_html};
+use native::MarketData;
+use serde::Serialize;
+use stats::Summary;
+
+// The numbers behind the cards, written to `summary.json`
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportSummary {
+    pub generated_at: i64,
+    #[serde(flatten)]
+    pub summary: Summary,
+    pub locked_on_chain: BigNum,
+    pub volume_24h: BigNum,
+    pub monthly_impressions: BigNum,
+    pub daily_impressions: BigNum,
+    pub yearly_impressions: BigNum,
+    // The same amounts in USD, at the prices of the price source, or at the static ones when
+    // it's unreachable; `null` if a deposit asset has no known price
+    pub dai_price_usd: Option<f64>,
+    pub total_deposit_usd: Option<BigNum>,
+    pub paid_out_usd: Option<BigNum>,
+    pub locked_on_chain_usd: Option<BigNum>,
+    pub volume_24h_usd: Option<BigNum>,
+}
+
+pub fn summary(data: &MarketData) -> ReportSummary {
+    let total = |resp: &AnalyticsResp| -> BigNum { resp.aggr.iter().map(|x| &x.value).sum() };
+    let valuation = Valuation {
+        currency: Currency::Usd,
+        prices: data.prices.as_ref(),
+    };
+    let volume_24h = total(&data.volume);
+    ReportSummary {
+        generated_at: data.time,
+        summary: stats::summary(&data.channels),
+        locked_on_chain: data.balance.result.clone(),
+        monthly_impressions: total(&data.impressions),
+        daily_impressions: total(&data.daily_impressions),
+        yearly_impressions: total(&data.yearly_impressions),
+        dai_price_usd: prices::usd_price(data.prices.as_ref(), DAI_ADDR),
+        total_deposit_usd: valuation.usd_total(
+            data.channels
+                .iter()
+                .map(|c| (c.deposit_asset.as_str(), c.deposit_amount.clone())),
+        ),
+        paid_out_usd: valuation.usd_total(
+            data.channels
+                .iter()
+                .map(|c| (c.deposit_asset.as_str(), c.status.balances_sum())),
+        ),
+        locked_on_chain_usd: valuation.usd(DAI_ADDR, &data.balance.result),
+        volume_24h_usd: valuation.usd(DAI_ADDR, &volume_24h),
+        volume_24h,
+    }
+}
+
+pub fn report_html(data: &MarketData) -> String {
+    let model = Model {
+        now: data.time,
+        last_loaded: data.time,
+        staleness_threshold: DEFAULT_STALENESS_THRESHOLD,
+        market_channels: Ready(data.channels.clone(), data.time),
+        balance: Ready(data.balance.clone(), data.time),
+        volume: Ready(data.volume.clone(), data.time),
+        impressions: Ready(data.impressions.clone(), data.time),
+        daily_impressions: Ready(data.daily_impressions.clone(), data.time),
+        yearly_impressions: Ready(data.yearly_impressions.clone(), data.time),
+        prices: match &data.prices {
+            Some(prices) => Ready(prices.clone(), data.time),
+            None => Loading,
+        },
+        ..Model::default()
+    };
+    let current = history::MetricsSnapshot::new(
+        data.time,
+        &data.channels,
+        Some(data.balance.result.clone()),
+    );
+    let channels = data
+        .channels
+        .iter()
+        .sorted_by(|x, y| y.deposit_amount.cmp(&x.deposit_amount))
+        .collect::<Vec<_>>();
+
+    // A page without a running explorer behind it has no use for links or controls
+    let freshness = Freshness {
+        interactive: false,
+        ..Freshness::new(&model)
+    };
+
+    let body = div![
+        summary_cards(freshness, &model, &current),
+        h2!["Campaigns"],
+        channel_table(
+            freshness,
+            data.time,
+            &Default::default(),
+            None,
+            &Default::default(),
+            &channels
+        ),
+        ad_unit_stats_table(
+            model.language,
+            freshness.valuation,
+            &stats::ad_type_stats(&data.channels),
+            AdTypeSort::default(),
+            true,
+            false
+        ),
+    ];
+    let generated_at = Utc.timestamp(data.time, 0).format("%Y-%m-%d %H:%M UTC");
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1, shrink-to-fit=no">
+    <link rel="stylesheet" href="./styles.css">
+    <title>AdEx Explorer</title>
+    <meta name="description" content="Statistics and metrics of the AdEx Network, as of {generated_at}">
+</head>
+<body>
+<p>{note}</p>
+{body}</body>
+</html>
+"#,
+        generated_at = generated_at,
+        note = escape(&format!(
+            "Generated at {}, see summary.json for the raw numbers",
+            generated_at
+        )),
+        body = to_html(&body),
+    )
+}
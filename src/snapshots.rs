@@ -0,0 +1,278 @@
+// Renders views for fixture models and compares the HTML to the golden snapshots in `fixtures/snapshots`
+// Run with `UPDATE_SNAPSHOTS=1` to write new snapshots or to accept changes, then commit them
+use super::*;
+
+use html::to_html;
+
+use std::fs;
+use std::path::PathBuf;
+use test_fixtures::{fixture, market_data};
+use types::{AnalyticsDataPoint, MarketStatusType};
+
+const NOW: i64 = 1_571_400_000;
+
+fn assert_snapshot(name: &str, node: &Node<Msg>) {
+    let html = to_html(node);
+
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/snapshots");
+    let path = dir.join(format!("{}.html", name));
+    let update = std::env::var("UPDATE_SNAPSHOTS").is_ok();
+    match (fs::read_to_string(&path), update) {
+        (Ok(golden), false) => {
+            if golden != html {
+                let new_path = dir.join(format!("{}.new.html", name));
+                fs::write(&new_path, &html).expect("unable to write the new snapshot");
+                panic!(
+                    "snapshot {} changed, compare it with {}",
+                    path.display(),
+                    new_path.display()
+                );
+            }
+        }
+        (_, true) => {
+            fs::create_dir_all(&dir).expect("unable to create the snapshots directory");
+            fs::write(&path, &html).expect("unable to write the snapshot");
+        }
+        (Err(e), false) => panic!(
+            "snapshot {} is missing ({}), run with UPDATE_SNAPSHOTS=1 to write it",
+            path.display(),
+            e
+        ),
+    }
+}
+
+fn analytics(values: &[u64]) -> AnalyticsResp {
+    AnalyticsResp {
+        aggr: values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| AnalyticsDataPoint {
+                value: BigNum::from(*value),
+                time: Utc.timestamp(NOW - 3600 * (values.len() - i) as i64, 0),
+            })
+            .collect(),
+    }
+}
+
+fn loaded_model(load_action: ActionLoad, market_channels: Vec<MarketChannel>) -> Model {
+    let dai = BigNum::from(1_000_000_000_000_000_000u64);
+    Model {
+        load_action,
+        now: NOW,
+        last_loaded: NOW,
+        staleness_threshold: DEFAULT_STALENESS_THRESHOLD,
+        market_channels: Ready(market_channels, NOW),
+        balance: Ready(
+            EtherscanBalResp {
+                result: &BigNum::from(1150u64) * &dai,
+            },
+            NOW,
+        ),
+        volume: Ready(
+            analytics(&[
+                30_000_000_000_000_000,
+                52_000_000_000_000_000,
+                41_000_000_000_000_000,
+                67_000_000_000_000_000,
+                58_000_000_000_000_000,
+            ]),
+            NOW - 60,
+        ),
+        impressions: Ready(analytics(&[120_000, 180_000, 150_000, 210_000]), NOW - 300),
+        daily_impressions: Ready(analytics(&[4_000, 7_500, 6_200, 8_100]), NOW - 60),
+        // Fetched a long time ago: flagged as outdated
+        yearly_impressions: Ready(analytics(&[900_000, 1_400_000, 2_100_000]), NOW - 7200),
+        ..Model::default()
+    }
+}
+
+// Copies of the fixture channels, with unique ids
+fn many_channels(count: usize) -> Vec<MarketChannel> {
+    let channels = fixture();
+    (0..count)
+        .map(|i| {
+            let mut channel = channels[i % channels.len()].clone();
+            channel.id = format!("0x{:064x}", i);
+            channel
+        })
+        .collect()
+}
+
+#[test]
+fn view_loading() {
+    assert_snapshot("view_loading", &view(&Model::default()));
+}
+
+#[test]
+fn view_market_unreachable() {
+    // Only the cached channels are available, and refreshing them failed
+    let model = Model {
+        now: NOW,
+        last_loaded: NOW - 86400,
+        staleness_threshold: DEFAULT_STALENESS_THRESHOLD,
+        market_channels: Stale(fixture(), NOW - 86400),
+        market_unreachable: true,
+        ..Model::default()
+    };
+    assert_snapshot("view_market_unreachable", &view(&model));
+}
+
+#[test]
+fn view_market_unreachable_without_cache() {
+    let model = Model {
+        now: NOW,
+        staleness_threshold: DEFAULT_STALENESS_THRESHOLD,
+        market_unreachable: true,
+        ..Model::default()
+    };
+    assert_snapshot("view_market_unreachable_without_cache", &view(&model));
+}
+
+#[test]
+fn view_empty() {
+    let model = loaded_model(ActionLoad::ChannelsAll, vec![]);
+    assert_snapshot("view_empty", &view(&model));
+}
+
+#[test]
+fn view_summary() {
+    let model = loaded_model(ActionLoad::Summary, fixture());
+    assert_snapshot("view_summary", &view(&model));
+}
+
+#[test]
+fn view_channels_all() {
+    let model = loaded_model(ActionLoad::ChannelsAll, fixture());
+    assert_snapshot("view_channels_all", &view(&model));
+}
+
+#[test]
+fn view_channels_all_german() {
+    let model = Model {
+        language: Language::German,
+        ..loaded_model(ActionLoad::ChannelsAll, fixture())
+    };
+    assert_snapshot("view_channels_all_german", &view(&model));
+}
+
+#[test]
+fn view_channels_all_in_usd() {
+    let prices = serde_json::from_str(include_str!("../fixtures/coingecko-prices.json"))
+        .expect("the fixture should be a valid price response");
+    let model = Model {
+        currency: Currency::Both,
+        prices: Ready(prices, NOW),
+        ..loaded_model(ActionLoad::ChannelsAll, fixture())
+    };
+    assert_snapshot("view_channels_all_in_usd", &view(&model));
+}
+
+#[test]
+fn view_channel_detail() {
+    let channels = fixture();
+    let id = channels[0].id.clone();
+    let model = loaded_model(ActionLoad::ChannelDetail(id), channels);
+    assert_snapshot("view_channel_detail", &view(&model));
+}
+
+#[test]
+fn view_thousands_of_channels() {
+    let model = loaded_model(ActionLoad::Summary, many_channels(3000));
+    assert_snapshot("view_thousands_of_channels", &view(&model));
+
+    // The full table is too large for a snapshot, so we only check that every channel is there
+    let model = loaded_model(ActionLoad::ChannelsAll, many_channels(3000));
+    let html = to_html(&view(&model));
+    assert_eq!(html.matches("href=\"/channel/0x").count(), 3000);
+}
+
+#[test]
+fn channel_table_rows() {
+    let mut channels = fixture();
+    // A channel the validators haven't checked in a while
+    channels[1].status.last_checked = Utc.timestamp(NOW - 3600, 0);
+    channels[1].status.status_type = MarketStatusType::Unhealthy;
+    // Compared with a refresh where the first channel had paid out less, the second was
+    // still active and the last one didn't exist yet
+    let mut previous = fixture();
+    previous.pop();
+    let earner = previous[0].status.balances.keys().next().cloned().unwrap();
+    previous[0].status.balances.insert(earner, BigNum::from(0));
+    assert_snapshot(
+        "channel_table",
+        &channel_table(
+            Freshness {
+                now: NOW,
+                threshold: DEFAULT_STALENESS_THRESHOLD,
+                lang: Language::English,
+                zone: Zone::Utc,
+                valuation: Valuation {
+                    currency: Currency::Dai,
+                    prices: None,
+                },
+                interactive: true,
+            },
+            NOW,
+            &Default::default(),
+            Some(&Default::default()),
+            &changes::channel_changes(&previous, &channels),
+            &channels.iter().collect::<Vec<_>>(),
+        ),
+    );
+}
+
+#[test]
+fn cards() {
+    let freshness = Freshness {
+        now: NOW,
+        threshold: DEFAULT_STALENESS_THRESHOLD,
+        lang: Language::English,
+        zone: Zone::Utc,
+        valuation: Valuation {
+            currency: Currency::Dai,
+            prices: None,
+        },
+        interactive: true,
+    };
+    let volume = Ready(analytics(&[1, 5, 3, 8, 2]), NOW - 30);
+    let count = |n: u64| Language::English.compact_count(&BigNum::from(n));
+
+    assert_snapshot(
+        "cards",
+        &div![
+            card("Loading", Loading, freshness),
+            card("Ready", Ready(count(42), NOW - 30), freshness),
+            card("Outdated", Ready(count(42), NOW - 3600), freshness),
+            card("Cached", Stale(count(42), NOW - 86400), freshness),
+            card_with_note(
+                "With note",
+                Ready(count(42), NOW),
+                Some("+2 since your last visit".to_string()),
+                freshness
+            ),
+            card("Compact", Ready(count(1_234_567), NOW), freshness),
+            volume_card("Volume", volume.as_ref().map(|_| count(16)), &volume, freshness),
+            volume_card("Volume loading", Loading, &Loading, freshness),
+        ],
+    );
+}
+
+#[test]
+fn static_report() {
+    // Nothing in it would work without the explorer running
+    let html = report::report_html(&market_data(NOW));
+    assert!(!html.contains("<a "));
+    assert!(!html.contains("<input"));
+    assert!(!html.contains("sortable"));
+    // but all the channels are listed
+    assert_eq!(html.matches("class=\"recent").count() + html.matches("class=\"not-recent").count(), 3);
+}
+
+#[test]
+fn static_report_escapes_market_data() {
+    let mut data = market_data(NOW);
+    data.channels[0].spec.ad_units[0].media_url = "https://x.y/a.png\"><script>alert('x')</script>".to_string();
+    let html = report::report_html(&data);
+    assert!(!html.contains("<script"));
+    assert!(html.contains("src=\"https://x.y/a.png&quot;&gt;&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt;\""));
+}
@@ -0,0 +1,327 @@
+// Aggregations over the market channels
+// Kept free of any rendering or browser APIs, so that it can be tested natively
+use super::types;
+
+use adex_domain::BigNum;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use types::{MarketChannel, MarketStatusType};
+
+// The numbers behind the summary cards
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Summary {
+    pub campaigns: usize,
+    pub ad_units: usize,
+    pub publishers: usize,
+    pub advertisers: usize,
+    pub total_deposit: BigNum,
+    pub paid_out: BigNum,
+}
+
+pub fn summary<'a, I>(channels: I) -> Summary
+where
+    I: IntoIterator<Item = &'a MarketChannel>,
+    I::IntoIter: Clone,
+{
+    let channels = channels.into_iter();
+
+    Summary {
+        campaigns: channels.clone().count(),
+        ad_units: channels
+            .clone()
+            .flat_map(|x| &x.spec.ad_units)
+            .map(|x| &x.ipfs)
+            .collect::<HashSet<_>>()
+            .len(),
+        publishers: publishers(channels.clone()).len(),
+        advertisers: channels
+            .clone()
+            .map(|x| x.creator.to_lowercase())
+            .collect::<HashSet<_>>()
+            .len(),
+        total_deposit: channels.clone().map(|x| &x.deposit_amount).sum(),
+        paid_out: channels.map(|x| x.status.balances_sum()).sum(),
+    }
+}
+
+// Everyone who earned from a channel, except for the advertiser who created it
+pub fn publishers<'a>(channels: impl Iterator<Item = &'a MarketChannel>) -> HashSet<String> {
+    channels
+        .flat_map(|x| {
+            x.status
+                .balances
+                .keys()
+                .map(|k| k.to_lowercase())
+                .filter(|k| *k != x.creator.to_lowercase())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+// Per ad type statistics, for the ad unit stats table
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdTypeStats {
+    pub ad_type: String,
+    pub campaigns: usize,
+    pub units: usize,
+    pub advertisers: usize,
+    pub publishers: usize,
+    pub avg_weighted_per_impression: BigNum,
+    pub cpm_range: CpmRange,
+    pub paid_out: BigNum,
+    pub total_active_vol: BigNum,
+    pub total_vol: BigNum,
+    // The same volumes by deposit asset (lowercase), so that each can be valued at its own price
+    pub by_asset: HashMap<String, Volumes>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Volumes {
+    pub paid_out: BigNum,
+    pub total_active_vol: BigNum,
+    pub total_vol: BigNum,
+}
+
+// Price per impression statistics, across the active campaigns of a single ad type
+#[derive(Clone, Debug, PartialEq)]
+pub struct CpmRange {
+    // The lowest minimum price
+    pub min: BigNum,
+    // The median of the minimum prices
+    pub median: BigNum,
+    // The highest maximum price
+    pub max: BigNum,
+}
+
+impl CpmRange {
+    fn new<'a>(channels: impl Iterator<Item = &'a MarketChannel> + Clone) -> Self {
+        let mut per_impression = channels
+            .clone()
+            .map(|x| &x.spec.min_per_impression)
+            .collect::<Vec<_>>();
+        per_impression.sort();
+        let len = per_impression.len();
+        if len == 0 {
+            return CpmRange {
+                min: BigNum::from(0),
+                median: BigNum::from(0),
+                max: BigNum::from(0),
+            };
+        }
+        let median = if len % 2 == 0 {
+            (per_impression[len / 2 - 1] + per_impression[len / 2]).div_floor(&2.into())
+        } else {
+            per_impression[len / 2].clone()
+        };
+        CpmRange {
+            min: per_impression[0].clone(),
+            median,
+            max: channels
+                .map(|x| &x.spec.max_per_impression)
+                .max()
+                .cloned()
+                .unwrap_or_else(|| BigNum::from(0)),
+        }
+    }
+}
+
+pub fn ad_type_stats<'a>(channels: impl IntoIterator<Item = &'a MarketChannel>) -> Vec<AdTypeStats> {
+    // A campaign may have multiple units of the same type,
+    // so we count units separately and only keep each campaign once
+    let units_by_type = channels
+        .into_iter()
+        .flat_map(|channel| {
+            channel
+                .spec
+                .ad_units
+                .iter()
+                .map(move |unit| (unit.ad_type.as_str(), channel))
+        })
+        .fold(
+            HashMap::<&str, (usize, Vec<&MarketChannel>)>::new(),
+            |mut by_type, (ad_type, channel)| {
+                let (units, all) = by_type
+                    .entry(ad_type)
+                    .or_insert_with(|| (0, Vec::new()));
+                *units += 1;
+                if !all.iter().any(|x| x.id == channel.id) {
+                    all.push(channel);
+                }
+
+                by_type
+            },
+        );
+
+    units_by_type
+        .into_iter()
+        .map(|(ad_type, (units, all))| {
+            let total_vol: BigNum = all.iter().map(|x| &x.deposit_amount).sum();
+            let paid_out: BigNum = all.iter().map(|x| x.status.balances_sum()).sum();
+
+            let advertisers = all
+                .iter()
+                .map(|x| x.creator.to_lowercase())
+                .collect::<HashSet<_>>();
+
+            let active = all
+                .iter()
+                .filter(|x| x.status.status_type == MarketStatusType::Active);
+            let total_active_vol: BigNum = active
+                .clone()
+                .map(|x| &x.deposit_amount - &x.status.balances_sum())
+                .sum();
+
+            let all_by_impression: BigNum = active
+                .clone()
+                .map(|x| &x.deposit_amount * &x.spec.min_per_impression)
+                .sum();
+
+            let all_deposits: BigNum = active.clone().map(|x| &x.deposit_amount).sum();
+
+            let avg_weighted_per_impression: BigNum = if all_deposits == BigNum::from(0) {
+                BigNum::from(0)
+            } else {
+                all_by_impression.div_floor(&all_deposits)
+            };
+
+            let by_asset = all.iter().fold(HashMap::<String, Volumes>::new(), |mut by_asset, x| {
+                let volumes = by_asset
+                    .entry(x.deposit_asset.to_lowercase())
+                    .or_insert_with(|| Volumes {
+                        paid_out: BigNum::from(0),
+                        total_active_vol: BigNum::from(0),
+                        total_vol: BigNum::from(0),
+                    });
+                let paid = x.status.balances_sum();
+                if x.status.status_type == MarketStatusType::Active {
+                    volumes.total_active_vol =
+                        &volumes.total_active_vol + &(&x.deposit_amount - &paid);
+                }
+                volumes.total_vol = &volumes.total_vol + &x.deposit_amount;
+                volumes.paid_out = &volumes.paid_out + &paid;
+
+                by_asset
+            });
+
+            AdTypeStats {
+                ad_type: ad_type.to_string(),
+                campaigns: all.len(),
+                units,
+                advertisers: advertisers.len(),
+                publishers: publishers(all.iter().cloned()).len(),
+                avg_weighted_per_impression,
+                cpm_range: CpmRange::new(active.clone().cloned()),
+                paid_out,
+                total_active_vol,
+                total_vol,
+                by_asset,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_fixtures::{dai, fixture};
+
+    #[test]
+    fn summary_of_market_response() {
+        let channels = fixture();
+        let summary = summary(&channels);
+
+        assert_eq!(summary.campaigns, 3);
+        // one unit is shared by two campaigns
+        assert_eq!(summary.ad_units, 4);
+        // the creators themselves are not publishers, and addresses are case insensitive
+        assert_eq!(summary.publishers, 2);
+        assert_eq!(summary.advertisers, 2);
+        assert_eq!(summary.total_deposit, dai(2000));
+        // including what the creator of a campaign paid to themselves
+        assert_eq!(summary.paid_out, dai(850));
+    }
+
+    #[test]
+    fn summary_of_no_channels() {
+        let channels: Vec<MarketChannel> = vec![];
+        let summary = summary(&channels);
+
+        assert_eq!(summary.campaigns, 0);
+        assert_eq!(summary.publishers, 0);
+        assert_eq!(summary.total_deposit, BigNum::from(0));
+        assert_eq!(summary.paid_out, BigNum::from(0));
+    }
+
+    #[test]
+    fn ad_type_stats_of_market_response() {
+        let channels = fixture();
+        let mut stats = ad_type_stats(&channels);
+        stats.sort_by(|x, y| x.ad_type.cmp(&y.ad_type));
+
+        assert_eq!(
+            stats.iter().map(|x| x.ad_type.as_str()).collect::<Vec<_>>(),
+            vec!["legacy_300x250", "legacy_728x90"]
+        );
+
+        let medium_rectangle = &stats[0];
+        assert_eq!(medium_rectangle.campaigns, 2);
+        assert_eq!(medium_rectangle.units, 3);
+        assert_eq!(medium_rectangle.advertisers, 1);
+        assert_eq!(medium_rectangle.total_vol, dai(1000));
+        // only active campaigns count towards the active volume and CPM
+        assert_eq!(medium_rectangle.total_active_vol, dai(550));
+        assert_eq!(
+            medium_rectangle.cpm_range.min,
+            BigNum::from(200_000_000_000_000u64)
+        );
+        // the highest maximum price, not the highest minimum one
+        assert_eq!(
+            medium_rectangle.cpm_range.max,
+            BigNum::from(300_000_000_000_000u64)
+        );
+        assert_eq!(
+            medium_rectangle.avg_weighted_per_impression,
+            BigNum::from(200_000_000_000_000u64)
+        );
+
+        let leaderboard = &stats[1];
+        assert_eq!(leaderboard.campaigns, 2);
+        assert_eq!(leaderboard.units, 2);
+        assert_eq!(leaderboard.advertisers, 2);
+        assert_eq!(leaderboard.publishers, 2);
+        assert_eq!(leaderboard.total_active_vol, dai(1150));
+        // weighted by deposit: (1000 * 0.0004 + 600 * 0.0002) / 1600
+        assert_eq!(
+            leaderboard.avg_weighted_per_impression,
+            BigNum::from(325_000_000_000_000u64)
+        );
+        // median of two values is their average
+        assert_eq!(
+            leaderboard.cpm_range.median,
+            BigNum::from(300_000_000_000_000u64)
+        );
+        assert_eq!(
+            leaderboard.cpm_range.max,
+            BigNum::from(400_000_000_000_000u64)
+        );
+    }
+
+    #[test]
+    fn ad_type_volumes_by_asset() {
+        let mut channels = fixture();
+        channels[0].deposit_asset = "0xSAI".to_string();
+        let stats = ad_type_stats(&channels);
+        let leaderboard = stats.iter().find(|x| x.ad_type == "legacy_728x90").unwrap();
+
+        assert_eq!(leaderboard.by_asset.len(), 2);
+        let sai = &leaderboard.by_asset["0xsai"];
+        assert_eq!(sai.total_vol, dai(1000));
+        let total = |f: fn(&Volumes) -> &BigNum| -> BigNum {
+            leaderboard.by_asset.values().map(f).sum()
+        };
+        assert_eq!(total(|x| &x.total_vol), leaderboard.total_vol);
+        assert_eq!(total(|x| &x.total_active_vol), leaderboard.total_active_vol);
+        assert_eq!(total(|x| &x.paid_out), leaderboard.paid_out);
+    }
+}
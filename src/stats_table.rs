@@ -1,91 +1,125 @@
-use super::{dai_readable, types, Msg};
+use super::{i18n, prices, stats, types, valued_amount, Msg};
 
 use adex_domain::BigNum;
+use i18n::Language;
 use lazysort::*;
+use prices::Valuation;
 use seed::prelude::*;
-use std::collections::HashMap;
-use types::{MarketChannel, MarketStatusType};
+use stats::{AdTypeStats, Volumes};
+use std::cmp::Ordering;
+use types::AdTypeSort;
 
-pub fn ad_unit_stats_table(channels: &[&MarketChannel]) -> Node<Msg> {
-    let units_by_type = channels
-        .iter()
-        .flat_map(|channel| {
-            channel
-                .spec
-                .ad_units
-                .iter()
-                .map(move |unit| (&unit.ad_type, channel))
-        })
-        .fold(
-            HashMap::<&str, Vec<&MarketChannel>>::new(),
-            |mut by_type, (ad_type, channel)| {
-                by_type
-                    .entry(&ad_type)
-                    .or_insert_with(Vec::new)
-                    .push(channel);
-
-                by_type
-            },
-        );
+fn cmp_by(x: &AdTypeStats, y: &AdTypeStats, sort: AdTypeSort) -> Ordering {
+    match sort {
+        AdTypeSort::AdType => x.ad_type.cmp(&y.ad_type),
+        AdTypeSort::Campaigns => y.campaigns.cmp(&x.campaigns),
+        AdTypeSort::Units => y.units.cmp(&x.units),
+        AdTypeSort::Advertisers => y.advertisers.cmp(&x.advertisers),
+        AdTypeSort::Publishers => y.publishers.cmp(&x.publishers),
+        AdTypeSort::WeightedCpm => y
+            .avg_weighted_per_impression
+            .cmp(&x.avg_weighted_per_impression),
+        AdTypeSort::PaidOut => y.paid_out.cmp(&x.paid_out),
+        AdTypeSort::ActiveVolume => y.total_active_vol.cmp(&x.total_active_vol),
+        AdTypeSort::TotalVolume => y.total_vol.cmp(&x.total_vol),
+    }
+}
 
-    let units_by_type_stats = units_by_type
+pub fn ad_unit_stats_table(
+    lang: Language,
+    valuation: Valuation,
+    stats: &[AdTypeStats],
+    sort: AdTypeSort,
+    include_inactive: bool,
+    // Without sorting, filtering and links, e.g. in the static report
+    interactive: bool,
+) -> Node<Msg> {
+    let units_by_type_stats = stats
         .iter()
-        .map(|(ad_type, all)| {
-            let total_vol: BigNum = all.iter().map(|x| &x.deposit_amount).sum();
-
-            let active = all
-                .iter()
-                .filter(|x| x.status.status_type == MarketStatusType::Active);
-            let total_active_vol: BigNum = active
-                .clone()
-                .map(|x| &x.deposit_amount - &x.status.balances_sum())
-                .sum();
-
-            let all_by_impression: BigNum = active
-                .clone()
-                .map(|x| &x.deposit_amount * &x.spec.min_per_impression)
-                .sum();
-
-            let all_deposits: BigNum = active.clone().map(|x| &x.deposit_amount).sum();
-
-            let avg_weighted_per_impression: BigNum = if all_deposits == BigNum::from(0) {
-                BigNum::from(0)
-            } else {
-                all_by_impression.div_floor(&all_deposits)
-            };
-
-            (
-                ad_type,
-                avg_weighted_per_impression,
-                total_active_vol,
-                total_vol,
-            )
-        })
-        .sorted_by(|x, y| y.1.cmp(&x.1))
+        .sorted_by(|x, y| cmp_by(x, y, sort))
         .collect::<Vec<_>>();
 
+    let header_cell = |label: &'static str, by: AdTypeSort| {
+        if !interactive {
+            return td![lang.t(label)];
+        }
+        td![
+            class![if by == sort { "sortable sorted" } else { "sortable" }],
+            simple_ev(Ev::Click, Msg::AdTypeSortSelected(by)),
+            lang.t(label)
+        ]
+    };
+
     let header = tr![
-        td!["Ad Size"],
-        //td!["Current CPM"],
-        td!["Active volume"],
-        td!["Total volume"]
+        header_cell("Ad Size", AdTypeSort::AdType),
+        header_cell("Campaigns", AdTypeSort::Campaigns),
+        header_cell("Units", AdTypeSort::Units),
+        header_cell("Advertisers", AdTypeSort::Advertisers),
+        header_cell("Publishers", AdTypeSort::Publishers),
+        header_cell("Weighted CPM", AdTypeSort::WeightedCpm),
+        td![lang.t("Min CPM")],
+        td![lang.t("Median min. CPM")],
+        td![lang.t("Max CPM")],
+        header_cell("Paid out", AdTypeSort::PaidOut),
+        header_cell("Active volume", AdTypeSort::ActiveVolume),
+        header_cell("Total volume", AdTypeSort::TotalVolume)
     ];
 
-    table![std::iter::once(header)
-        .chain(
-            units_by_type_stats
-                .iter()
-                .filter(|(_, _, total_active_vol, _)| { total_active_vol > &BigNum::from(0) })
-                .map(
-                    |(ad_type, avg_weighted_per_impression, total_active_vol, total_vol)| {
+    div![
+        if interactive {
+            label![
+                input![
+                    if include_inactive {
+                        attrs! { At::Type => "checkbox"; At::Checked => true }
+                    } else {
+                        attrs! { At::Type => "checkbox" }
+                    },
+                    simple_ev(Ev::Click, Msg::ToggleInactiveAdTypes)
+                ],
+                lang.t("Include inactive ad types")
+            ]
+        } else {
+            seed::empty()
+        },
+        table![std::iter::once(header)
+            .chain(
+                units_by_type_stats
+                    .iter()
+                    .filter(|stats| include_inactive || stats.total_active_vol > BigNum::from(0))
+                    .map(|stats| {
+                        // Each deposit asset at its own price
+                        let volume = |amount: &BigNum, of: fn(&Volumes) -> &BigNum| {
+                            let usd = valuation.usd_total(
+                                stats
+                                    .by_asset
+                                    .iter()
+                                    .map(|(asset, volumes)| (asset.as_str(), of(volumes).clone())),
+                            );
+                            valued_amount(lang, valuation, amount, usd)
+                        };
                         tr![
-                            td![ad_type],
-                            //td![dai_readable(&(avg_weighted_per_impression * &1000.into()))],
-                            td![dai_readable(&total_active_vol)],
-                            td![dai_readable(&total_vol)],
+                            td![if interactive {
+                                a![
+                                    attrs! { At::Href => format!("/ad-type/{}", stats.ad_type) },
+                                    &stats.ad_type
+                                ]
+                            } else {
+                                span![&stats.ad_type]
+                            }],
+                            td![lang.count(stats.campaigns)],
+                            td![lang.count(stats.units)],
+                            td![lang.count(stats.advertisers)],
+                            td![lang.count(stats.publishers)],
+                            td![lang.cpm(&stats.avg_weighted_per_impression)],
+                            td![lang.cpm(&stats.cpm_range.min)],
+                            td![lang.cpm(&stats.cpm_range.median)],
+                            td![lang.cpm(&stats.cpm_range.max)],
+                            td![volume(&stats.paid_out, |x| &x.paid_out)],
+                            td![volume(&stats.total_active_vol, |x| &x.total_active_vol)],
+                            td![volume(&stats.total_vol, |x| &x.total_vol)],
                         ]
-                    }
-                )
-        )
-        .collect::<Vec<Node<Msg>>>()]
+                    })
+            )
+            .collect::<Vec<Node<Msg>>>()]
+    ]
 }
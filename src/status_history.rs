@@ -0,0 +1,163 @@
+// The statuses each channel went through, as observed on every refresh, persisted locally
+use super::types::{MarketChannel, MarketStatusType};
+use super::{cache, i18n, Msg};
+
+use chrono::{TimeZone, Utc};
+use i18n::{Language, Zone};
+use seed::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const STATUS_HISTORY_KEY: &str = "adex-explorer-status-history";
+// A flapping channel shouldn't fill up the local storage
+const MAX_TRANSITIONS: usize = 100;
+
+// A status, as first seen at `time`, the time the validators checked the channel
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StatusObservation {
+    pub time: i64,
+    pub status: MarketStatusType,
+}
+
+// By channel id, oldest first
+pub type StatusHistory = HashMap<String, Vec<StatusObservation>>;
+
+pub fn load() -> StatusHistory {
+    cache::read(STATUS_HISTORY_KEY).unwrap_or_default()
+}
+
+pub fn save(history: &StatusHistory) {
+    cache::write(STATUS_HISTORY_KEY, history);
+}
+
+// Records the statuses that differ from the last observed ones; channels which are no longer
+// on the market are forgotten. Returns whether anything changed
+pub fn observe(history: &mut StatusHistory, channels: &[MarketChannel]) -> bool {
+    let before = history.len();
+    history.retain(|id, _| channels.iter().any(|channel| &channel.id == id));
+    let mut changed = history.len() != before;
+
+    for channel in channels {
+        let observations = history.entry(channel.id.clone()).or_default();
+        let status = &channel.status.status_type;
+        if observations.last().map(|last| &last.status) == Some(status) {
+            continue;
+        }
+        observations.push(StatusObservation {
+            time: channel.status.last_checked.timestamp(),
+            status: status.clone(),
+        });
+        if observations.len() > MAX_TRANSITIONS {
+            let excess = observations.len() - MAX_TRANSITIONS;
+            observations.drain(..excess);
+        }
+        changed = true;
+    }
+    changed
+}
+
+fn duration_readable(lang: Language, seconds: i64) -> String {
+    match seconds {
+        x if x < 3600 => lang.tn("{} minute", "{} minutes", x / 60),
+        x if x < 86400 => lang.tn("{} hour", "{} hours", x / 3600),
+        x => lang.tn("{} day", "{} days", x / 86400),
+    }
+}
+
+// Newest first, with how long each status lasted
+pub fn status_timeline(
+    lang: Language,
+    zone: Zone,
+    now_seconds: i64,
+    observations: &[StatusObservation],
+) -> Node<Msg> {
+    if observations.len() < 2 {
+        return p![lang.t("No status changes observed yet")];
+    }
+    let ends = observations
+        .iter()
+        .skip(1)
+        .map(|next| next.time)
+        .chain(std::iter::once(now_seconds));
+    let entries = observations
+        .iter()
+        .zip(ends)
+        .enumerate()
+        .map(|(i, (observation, end))| {
+            let label = match i.checked_sub(1).map(|previous| &observations[previous].status) {
+                Some(previous) => format!(
+                    "{} → {}",
+                    lang.t(previous.name()),
+                    lang.t(observation.status.name())
+                ),
+                None => lang.tf("{} (first seen)", &[&lang.t(observation.status.name())]),
+            };
+            li![
+                span![
+                    class!["time"],
+                    lang.date_time(zone, &Utc.timestamp(observation.time, 0))
+                ],
+                span![class!["status"], label],
+                span![
+                    class!["duration"],
+                    lang.tf("for {}", &[&duration_readable(lang, (end - observation.time).max(0))])
+                ],
+            ]
+        })
+        .collect::<Vec<_>>();
+    ol![
+        class!["status-timeline"],
+        entries.into_iter().rev().collect::<Vec<_>>()
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_fixtures::fixture;
+
+    #[test]
+    fn records_transitions_only() {
+        let mut channels = fixture();
+        let mut history = StatusHistory::new();
+        assert!(observe(&mut history, &channels));
+        assert_eq!(history.len(), channels.len());
+        assert!(!observe(&mut history, &channels));
+
+        let id = channels[1].id.clone();
+        let active = channels[1].status.status_type.clone();
+        channels[1].status.status_type = MarketStatusType::Unhealthy;
+        assert!(observe(&mut history, &channels));
+        channels[1].status.status_type = active.clone();
+        assert!(observe(&mut history, &channels));
+        let statuses = history[&id].iter().map(|x| x.status.clone()).collect::<Vec<_>>();
+        assert_eq!(statuses, vec![active.clone(), MarketStatusType::Unhealthy, active]);
+    }
+
+    #[test]
+    fn forgets_removed_channels() {
+        let mut channels = fixture();
+        let mut history = StatusHistory::new();
+        observe(&mut history, &channels);
+        let removed = channels.pop().unwrap();
+        assert!(observe(&mut history, &channels));
+        assert!(!history.contains_key(&removed.id));
+    }
+
+    #[test]
+    fn keeps_the_latest_transitions() {
+        let mut channels = fixture();
+        let mut history = StatusHistory::new();
+        for i in 0..MAX_TRANSITIONS + 10 {
+            channels[0].status.status_type = if i % 2 == 0 {
+                MarketStatusType::Active
+            } else {
+                MarketStatusType::Unhealthy
+            };
+            observe(&mut history, &channels);
+        }
+        let observations = &history[&channels[0].id];
+        assert_eq!(observations.len(), MAX_TRANSITIONS);
+        assert_eq!(observations.last().unwrap().status, MarketStatusType::Unhealthy);
+    }
+}
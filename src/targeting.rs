@@ -0,0 +1,205 @@
+use super::i18n::Language;
+use super::prices::Valuation;
+use super::{types, valued_amount, Msg};
+
+use adex_domain::{BigNum, TargetingTag};
+use lazysort::*;
+use seed::prelude::*;
+use std::collections::{HashMap, HashSet};
+use types::{MarketChannel, MarketStatusType};
+
+// The domain type only exposes the score through serde, as a number from 0 to 100
+fn score(tag: &TargetingTag) -> Option<u64> {
+    serde_json::to_value(&tag.score)
+        .ok()
+        .and_then(|value| value.as_u64())
+}
+
+// A targeting rule: the tag, and how strongly it's targeted
+fn rule_readable(lang: Language, tag: &TargetingTag) -> String {
+    match score(tag) {
+        Some(score) => lang.tf("{} (score: {})", &[&tag.tag, &score]),
+        None => tag.tag.clone(),
+    }
+}
+
+// All targeting tags of a campaign: the ones of the campaign itself and of all of its units
+fn campaign_tags(channel: &MarketChannel) -> impl Iterator<Item = &TargetingTag> {
+    channel.spec.targeting.iter().chain(
+        channel
+            .spec
+            .ad_units
+            .iter()
+            .flat_map(|unit| unit.targeting.iter()),
+    )
+}
+
+// How the active campaigns target a tag
+struct TagStats<'a> {
+    tag: &'a str,
+    channels: Vec<&'a MarketChannel>,
+    total_deposit: BigNum,
+    // The lowest and the highest score of the rules targeting it
+    score_range: Option<(u64, u64)>,
+}
+
+// The most targeted tags first
+fn tag_stats<'a>(channels: &[&'a MarketChannel]) -> Vec<TagStats<'a>> {
+    let channels_by_tag = channels
+        .iter()
+        .cloned()
+        .filter(|channel| channel.status.status_type == MarketStatusType::Active)
+        .flat_map(|channel| {
+            // each campaign is counted once per tag, even if multiple units target it
+            campaign_tags(channel)
+                .map(|x| x.tag.as_str())
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .map(move |tag| (tag, channel))
+        })
+        .fold(
+            HashMap::<&str, Vec<&'a MarketChannel>>::new(),
+            |mut by_tag, (tag, channel)| {
+                by_tag.entry(tag).or_insert_with(Vec::new).push(channel);
+
+                by_tag
+            },
+        );
+
+    channels_by_tag
+        .into_iter()
+        .map(|(tag, channels)| {
+            let total_deposit: BigNum = channels.iter().map(|x| &x.deposit_amount).sum();
+            // The scores of all the rules targeting the tag, whether on campaigns or on units
+            let scores = channels
+                .iter()
+                .flat_map(|channel| campaign_tags(channel))
+                .filter(|x| x.tag == tag)
+                .filter_map(score);
+            let score_range = scores
+                .clone()
+                .min()
+                .and_then(|min| scores.max().map(|max| (min, max)));
+            TagStats {
+                tag,
+                channels,
+                total_deposit,
+                score_range,
+            }
+        })
+        .sorted_by(|x, y| {
+            y.channels
+                .len()
+                .cmp(&x.channels.len())
+                .then_with(|| y.total_deposit.cmp(&x.total_deposit))
+        })
+        .collect()
+}
+
+pub fn targeting_table(
+    lang: Language,
+    valuation: Valuation,
+    channels: &[&MarketChannel],
+) -> Node<Msg> {
+
+    let header = tr![
+        td![lang.t("Tag")],
+        td![lang.t("Active campaigns")],
+        td![lang.t("Total deposits")],
+        td![lang.t("Scores")]
+    ];
+
+    table![std::iter::once(header)
+        .chain(
+            tag_stats(channels)
+                .iter()
+                .map(|stats| {
+                    // Valued channel by channel, at the price of each deposit asset
+                    let total_deposit_usd = valuation.usd_total(
+                        stats
+                            .channels
+                            .iter()
+                            .map(|c| (c.deposit_asset.as_str(), c.deposit_amount.clone())),
+                    );
+                    tr![
+                        td![stats.tag],
+                        td![lang.count(stats.channels.len())],
+                        td![valued_amount(lang, valuation, &stats.total_deposit, total_deposit_usd)],
+                        td![match stats.score_range {
+                            Some((min, max)) if min == max => min.to_string(),
+                            Some((min, max)) => format!("{} - {}", min, max),
+                            None => lang.t("N/A").to_string(),
+                        }],
+                    ]
+                })
+        )
+        .collect::<Vec<Node<Msg>>>()]
+}
+
+pub fn channel_targeting(lang: Language, channel: &MarketChannel) -> Node<Msg> {
+    let tags = |targeting: &[TargetingTag]| -> Node<Msg> {
+        if targeting.is_empty() {
+            return span![lang.t("No targeting")];
+        }
+        ul![targeting
+            .iter()
+            .map(|x| li![rule_readable(lang, x)])
+            .collect::<Vec<Node<Msg>>>()]
+    };
+
+    div![
+        h3![lang.t("Campaign targeting")],
+        tags(&channel.spec.targeting),
+        channel
+            .spec
+            .ad_units
+            .iter()
+            .map(|unit| {
+                div![
+                    h4![format!("{} ({})", unit.ad_type, unit.ipfs)],
+                    tags(&unit.targeting),
+                ]
+            })
+            .collect::<Vec<Node<Msg>>>()
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_fixtures::{dai, fixture};
+
+    fn tag(json: &str) -> TargetingTag {
+        serde_json::from_str(json).expect("a valid targeting tag")
+    }
+
+    #[test]
+    fn tags_of_active_campaigns() {
+        let mut channels = fixture();
+        // the first campaign targets "crypto" with a score of 100
+        channels[1].spec.targeting = vec![tag(r#"{"tag": "games", "score": 50}"#)];
+        channels[1].spec.ad_units[0].targeting = vec![tag(r#"{"tag": "crypto", "score": 20}"#)];
+        channels[1].spec.ad_units[1].targeting = vec![tag(r#"{"tag": "crypto", "score": 60}"#)];
+        // not active
+        channels[2].spec.targeting = vec![tag(r#"{"tag": "crypto", "score": 5}"#)];
+
+        let stats = tag_stats(&channels.iter().collect::<Vec<_>>());
+        assert_eq!(stats.iter().map(|x| x.tag).collect::<Vec<_>>(), vec!["crypto", "games"]);
+
+        // each campaign is counted once, however many of its units target the tag
+        assert_eq!(stats[0].channels.len(), 2);
+        assert_eq!(stats[0].total_deposit, dai(1600));
+        assert_eq!(stats[0].score_range, Some((20, 100)));
+
+        assert_eq!(stats[1].channels.len(), 1);
+        assert_eq!(stats[1].total_deposit, dai(600));
+        assert_eq!(stats[1].score_range, Some((50, 50)));
+    }
+
+    #[test]
+    fn no_tags() {
+        let channels = fixture();
+        let stats = tag_stats(&channels.iter().skip(1).collect::<Vec<_>>());
+        assert!(stats.is_empty());
+    }
+}
@@ -0,0 +1,37 @@
+// Helpers shared by the native tests
+#[cfg(not(target_arch = "wasm32"))]
+use super::native::MarketData;
+use super::types::MarketChannel;
+
+use adex_domain::BigNum;
+use serde::de::DeserializeOwned;
+
+fn parse<T: DeserializeOwned>(json: &str) -> T {
+    serde_json::from_str(json).expect("the fixtures should be valid responses")
+}
+
+// The market response bundled in `fixtures`
+pub fn fixture() -> Vec<MarketChannel> {
+    parse(include_str!("../fixtures/market-campaigns.json"))
+}
+
+// Everything the native tools fetch, from the fixtures; without prices, as when the
+// price source is unreachable
+#[cfg(not(target_arch = "wasm32"))]
+pub fn market_data(time: i64) -> MarketData {
+    MarketData {
+        time,
+        channels: fixture(),
+        balance: parse(include_str!("../fixtures/etherscan-balance.json")),
+        volume: parse(include_str!("../fixtures/analytics-daily-volume.json")),
+        impressions: parse(include_str!("../fixtures/analytics-monthly-impressions.json")),
+        daily_impressions: parse(include_str!("../fixtures/analytics-daily-impressions.json")),
+        yearly_impressions: parse(include_str!("../fixtures/analytics-yearly-impressions.json")),
+        prices: None,
+    }
+}
+
+// In whole DAI
+pub fn dai(amount: u64) -> BigNum {
+    &BigNum::from(amount) * &BigNum::from(1_000_000_000_000_000_000u64)
+}
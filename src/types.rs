@@ -1,16 +1,17 @@
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
 use adex_domain::{BigNum, ChannelSpec};
-use chrono::serde::ts_milliseconds;
+use chrono::serde::{ts_milliseconds, ts_seconds};
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 // Volume response from the validator
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct AnalyticsResp {
     pub aggr: Vec<AnalyticsDataPoint>,
 }
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct AnalyticsDataPoint {
     pub value: BigNum,
     #[serde(with = "ts_milliseconds")]
@@ -18,21 +19,77 @@ pub struct AnalyticsDataPoint {
 }
 
 // Etherscan API
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct EtherscanBalResp {
     pub result: BigNum,
 }
 
+// Token prices, e.g. from CoinGecko: by lowercase token address
+pub type PricesResp = HashMap<String, TokenPrice>;
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TokenPrice {
+    pub usd: f64,
+}
+
 // Model
 pub enum Loadable<T> {
     Loading,
-    Ready(T),
+    // Freshly fetched, with the time (in seconds) it was fetched at
+    Ready(T, i64),
+    // Served from the local cache, with the time (in seconds) it was fetched at
+    Stale(T, i64),
 }
 impl<T> Default for Loadable<T> {
     fn default() -> Self {
         Loadable::Loading
     }
 }
+impl<T> Loadable<T> {
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Loadable::Loading => None,
+            Loadable::Ready(value, _) | Loadable::Stale(value, _) => Some(value),
+        }
+    }
+
+    pub fn into_value(self) -> Option<T> {
+        match self {
+            Loadable::Loading => None,
+            Loadable::Ready(value, _) | Loadable::Stale(value, _) => Some(value),
+        }
+    }
+
+    // Only what was fetched in this session, not what was restored from the cache
+    pub fn fetched(&self) -> Option<&T> {
+        match self {
+            Loadable::Ready(value, _) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn fetched_at(&self) -> Option<i64> {
+        match self {
+            Loadable::Loading => None,
+            Loadable::Ready(_, time) | Loadable::Stale(_, time) => Some(*time),
+        }
+    }
+
+    pub fn as_ref(&self) -> Loadable<&T> {
+        match self {
+            Loadable::Loading => Loadable::Loading,
+            Loadable::Ready(value, time) => Loadable::Ready(value, *time),
+            Loadable::Stale(value, time) => Loadable::Stale(value, *time),
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Loadable<U> {
+        match self {
+            Loadable::Loading => Loadable::Loading,
+            Loadable::Ready(value, time) => Loadable::Ready(f(value), time),
+            Loadable::Stale(value, time) => Loadable::Stale(f(value), time),
+        }
+    }
+}
 
 #[derive(Clone, Copy)]
 pub enum ChannelSort {
@@ -47,8 +104,37 @@ impl Default for ChannelSort {
     }
 }
 
+impl ChannelSort {
+    pub fn compare(self, x: &MarketChannel, y: &MarketChannel) -> Ordering {
+        match self {
+            ChannelSort::Deposit => y.deposit_amount.cmp(&x.deposit_amount),
+            ChannelSort::Status => x.status.status_type.cmp(&y.status.status_type),
+            ChannelSort::Created => y.spec.created.cmp(&x.spec.created),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AdTypeSort {
+    AdType,
+    Campaigns,
+    Units,
+    Advertisers,
+    Publishers,
+    WeightedCpm,
+    PaidOut,
+    ActiveVolume,
+    TotalVolume,
+}
+
+impl Default for AdTypeSort {
+    fn default() -> Self {
+        AdTypeSort::WeightedCpm
+    }
+}
+
 // Data structs specific to the market
-#[derive(Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum MarketStatusType {
     Initializing,
     Waiting,
@@ -63,7 +149,48 @@ pub enum MarketStatusType {
     Exhausted,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+impl MarketStatusType {
+    pub const ALL: [MarketStatusType; 11] = [
+        MarketStatusType::Initializing,
+        MarketStatusType::Waiting,
+        MarketStatusType::Invalid,
+        MarketStatusType::Ready,
+        MarketStatusType::Active,
+        MarketStatusType::Offline,
+        MarketStatusType::Disconnected,
+        MarketStatusType::Unhealthy,
+        MarketStatusType::Withdraw,
+        MarketStatusType::Expired,
+        MarketStatusType::Exhausted,
+    ];
+
+    // The English name, to be translated
+    pub fn name(&self) -> &'static str {
+        match self {
+            MarketStatusType::Initializing => "Initializing",
+            MarketStatusType::Waiting => "Waiting",
+            MarketStatusType::Invalid => "Invalid",
+            MarketStatusType::Ready => "Ready",
+            MarketStatusType::Active => "Active",
+            MarketStatusType::Offline => "Offline",
+            MarketStatusType::Disconnected => "Disconnected",
+            MarketStatusType::Unhealthy => "Unhealthy",
+            MarketStatusType::Withdraw => "Withdraw",
+            MarketStatusType::Expired => "Expired",
+            MarketStatusType::Exhausted => "Exhausted",
+        }
+    }
+
+    // Finished channels are only listed with all the channels
+    pub fn is_finished(&self) -> bool {
+        match self {
+            MarketStatusType::Expired | MarketStatusType::Exhausted => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct MarketStatus {
     #[serde(rename = "name")]
@@ -81,7 +208,7 @@ impl MarketStatus {
     }
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct MarketChannel {
     pub id: String,
@@ -89,5 +216,7 @@ pub struct MarketChannel {
     pub deposit_asset: String,
     pub deposit_amount: BigNum,
     pub status: MarketStatus,
+    #[serde(with = "ts_seconds")]
+    pub valid_until: DateTime<Utc>,
     pub spec: ChannelSpec,
 }
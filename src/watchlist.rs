@@ -0,0 +1,290 @@
+// Starred channels, advertisers and publishers, persisted locally
+use super::changes::{self, ChannelChange};
+use super::i18n::Language;
+use super::prices::Valuation;
+use super::types::MarketChannel;
+use super::{cache, dai_amount, paid_percentage, valued_amount, Msg};
+
+use adex_domain::BigNum;
+use seed::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+const WATCHLIST_KEY: &str = "adex-explorer-watchlist";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WatchKind {
+    Channel,
+    Advertiser,
+    Publisher,
+}
+
+// Ids and addresses are kept in lowercase
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Watchlist {
+    pub channels: BTreeSet<String>,
+    pub advertisers: BTreeSet<String>,
+    pub publishers: BTreeSet<String>,
+}
+
+impl Watchlist {
+    fn set_mut(&mut self, kind: WatchKind) -> &mut BTreeSet<String> {
+        match kind {
+            WatchKind::Channel => &mut self.channels,
+            WatchKind::Advertiser => &mut self.advertisers,
+            WatchKind::Publisher => &mut self.publishers,
+        }
+    }
+
+    pub fn contains(&self, kind: WatchKind, id: &str) -> bool {
+        let set = match kind {
+            WatchKind::Channel => &self.channels,
+            WatchKind::Advertiser => &self.advertisers,
+            WatchKind::Publisher => &self.publishers,
+        };
+        set.contains(&id.to_lowercase())
+    }
+
+    pub fn toggle(&mut self, kind: WatchKind, id: &str) {
+        let id = id.to_lowercase();
+        let set = self.set_mut(kind);
+        if !set.remove(&id) {
+            set.insert(id);
+        }
+    }
+}
+
+pub fn load() -> Watchlist {
+    cache::read(WATCHLIST_KEY).unwrap_or_default()
+}
+
+pub fn save(watchlist: &Watchlist) {
+    cache::write(WATCHLIST_KEY, watchlist);
+}
+
+pub fn star(lang: Language, watchlist: &Watchlist, kind: WatchKind, id: &str) -> Node<Msg> {
+    let starred = watchlist.contains(kind, id);
+    span![
+        class![if starred { "star starred" } else { "star" }],
+        attrs! { At::Title => lang.t(if starred { "Remove from the watchlist" } else { "Add to the watchlist" }) },
+        simple_ev(Ev::Click, Msg::ToggleWatch(kind, id.to_string())),
+        if starred { "★" } else { "☆" }
+    ]
+}
+
+fn or_dash(text: String) -> String {
+    if text.is_empty() {
+        "-".to_string()
+    } else {
+        text
+    }
+}
+
+pub fn watchlist_page(
+    lang: Language,
+    valuation: Valuation,
+    watchlist: &Watchlist,
+    channels: &[MarketChannel],
+    previous: Option<&[MarketChannel]>,
+) -> Node<Msg> {
+    let changes = previous
+        .map(|previous| changes::channel_changes(previous, channels))
+        .unwrap_or_default();
+
+    if watchlist.channels.is_empty()
+        && watchlist.advertisers.is_empty()
+        && watchlist.publishers.is_empty()
+    {
+        return div![
+            h2![lang.t("Watchlist")],
+            p![lang.t("Star channels, advertisers and publishers (☆) to watch them here")]
+        ];
+    }
+
+    div![
+        h2![lang.t("Watchlist")],
+        watched_channels(lang, valuation, watchlist, channels, &changes),
+        watched_advertisers(lang, valuation, watchlist, channels, &changes),
+        watched_publishers(lang, valuation, watchlist, channels, previous),
+    ]
+}
+
+fn watched_channels(
+    lang: Language,
+    valuation: Valuation,
+    watchlist: &Watchlist,
+    channels: &[MarketChannel],
+    changes: &HashMap<&str, ChannelChange>,
+) -> Node<Msg> {
+    if watchlist.channels.is_empty() {
+        return seed::empty();
+    }
+    let header = tr![
+        td![],
+        td![lang.t("Channel")],
+        td![lang.t("Status")],
+        td![lang.t("Paid")],
+        td![lang.t("Paid - %")],
+        td![lang.t("Changes since last refresh")],
+    ];
+    let rows = watchlist.channels.iter().map(|id| {
+        match channels.iter().find(|channel| channel.id.eq_ignore_ascii_case(id)) {
+            Some(channel) => {
+                let paid = channel.status.balances_sum();
+                tr![
+                    td![star(lang, watchlist, WatchKind::Channel, id)],
+                    td![a![attrs! {At::Href => format!("/channel/{}", channel.id)}, channel.id.clone()]],
+                    td![lang.t(channel.status.status_type.name())],
+                    td![
+                        dai_amount(lang, valuation, &channel.deposit_asset, &paid),
+                        " / ",
+                        dai_amount(lang, valuation, &channel.deposit_asset, &channel.deposit_amount)
+                    ],
+                    td![paid_percentage(&paid, &channel.deposit_amount)],
+                    td![or_dash(
+                        changes
+                            .get(channel.id.as_str())
+                            .map(|change| change.describe(lang))
+                            .unwrap_or_default()
+                    )],
+                ]
+            }
+            None => tr![
+                td![star(lang, watchlist, WatchKind::Channel, id)],
+                td![id.clone()],
+                td![lang.t("Not on the market")],
+                td![],
+                td![],
+                td![],
+            ],
+        }
+    });
+
+    div![
+        h3![lang.t("Channels")],
+        table![std::iter::once(header).chain(rows).collect::<Vec<_>>()]
+    ]
+}
+
+fn watched_advertisers(
+    lang: Language,
+    valuation: Valuation,
+    watchlist: &Watchlist,
+    channels: &[MarketChannel],
+    changes: &HashMap<&str, ChannelChange>,
+) -> Node<Msg> {
+    if watchlist.advertisers.is_empty() {
+        return seed::empty();
+    }
+    let header = tr![
+        td![],
+        td![lang.t("Advertiser")],
+        td![lang.t("Campaigns")],
+        td![lang.t("Finished")],
+        td![lang.t("Deposits")],
+        td![lang.t("Paid out")],
+        td![lang.t("Changes since last refresh")],
+    ];
+    let rows = watchlist.advertisers.iter().map(|address| {
+        let created = channels
+            .iter()
+            .filter(|channel| channel.creator.eq_ignore_ascii_case(address))
+            .collect::<Vec<_>>();
+        let deposits: BigNum = created.iter().map(|channel| &channel.deposit_amount).sum();
+        let paid_out: BigNum = created.iter().map(|channel| channel.status.balances_sum()).sum();
+        // Valued channel by channel, at the price of each deposit asset
+        let deposits_usd = valuation.usd_total(
+            created
+                .iter()
+                .map(|c| (c.deposit_asset.as_str(), c.deposit_amount.clone())),
+        );
+        let paid_out_usd = valuation.usd_total(
+            created
+                .iter()
+                .map(|c| (c.deposit_asset.as_str(), c.status.balances_sum())),
+        );
+        tr![
+            td![star(lang, watchlist, WatchKind::Advertiser, address)],
+            td![address.clone()],
+            td![lang.count(created.len())],
+            td![lang.count(
+                created
+                    .iter()
+                    .filter(|channel| channel.status.status_type.is_finished())
+                    .count()
+            )],
+            td![valued_amount(lang, valuation, &deposits, deposits_usd)],
+            td![valued_amount(lang, valuation, &paid_out, paid_out_usd)],
+            td![or_dash(
+                changes::summarize(
+                    created
+                        .iter()
+                        .filter_map(|channel| changes.get(channel.id.as_str()))
+                )
+                .describe(lang)
+            )],
+        ]
+    });
+
+    div![
+        h3![lang.t("Advertisers")],
+        table![std::iter::once(header).chain(rows).collect::<Vec<_>>()]
+    ]
+}
+
+fn watched_publishers(
+    lang: Language,
+    valuation: Valuation,
+    watchlist: &Watchlist,
+    channels: &[MarketChannel],
+    previous: Option<&[MarketChannel]>,
+) -> Node<Msg> {
+    if watchlist.publishers.is_empty() {
+        return seed::empty();
+    }
+    let header = tr![
+        td![],
+        td![lang.t("Publisher")],
+        td![lang.t("Campaigns")],
+        td![lang.t("Earned")],
+        td![lang.t("Changes since last refresh")],
+    ];
+    let rows = watchlist.publishers.iter().map(|address| {
+        let earned = changes::earnings(channels.iter(), address);
+        // Valued channel by channel, at the price of each deposit asset
+        let earned_usd = |channels: &[MarketChannel]| {
+            valuation.usd_total(channels.iter().map(|c| {
+                (c.deposit_asset.as_str(), changes::earnings(std::iter::once(c), address))
+            }))
+        };
+        tr![
+            td![star(lang, watchlist, WatchKind::Publisher, address)],
+            td![address.clone()],
+            td![lang.count(
+                channels
+                    .iter()
+                    .filter(|channel| !channel.creator.eq_ignore_ascii_case(address)
+                        && channel.status.balances.keys().any(|earner| earner.eq_ignore_ascii_case(address)))
+                    .count()
+            )],
+            td![valued_amount(lang, valuation, &earned, earned_usd(channels))],
+            td![match previous.map(|previous| (previous, changes::earnings(previous.iter(), address))) {
+                Some((previous, before)) if earned > before => {
+                    let increase = &earned - &before;
+                    let increase_usd = match (earned_usd(channels), earned_usd(previous)) {
+                        (Some(now), Some(then)) if now > then => Some(&now - &then),
+                        _ => None,
+                    };
+                    let increase = valuation.amount_with_usd(lang, &increase, increase_usd);
+                    lang.tf("+{} earned", &[&increase.short])
+                }
+                _ => "-".to_string(),
+            }],
+        ]
+    });
+
+    div![
+        h3![lang.t("Publishers")],
+        table![std::iter::once(header).chain(rows).collect::<Vec<_>>()]
+    ]
+}